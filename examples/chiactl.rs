@@ -11,12 +11,13 @@ use anyhow::Result;
 use chia_client::{
     fullnode,
     models::fullnode::MemPoolItem,
+    types::{Address, Mojos, Unit},
     util::{decode_puzzle_hash, encode_puzzle_hash, mojo_to_xch},
     Client, ClientBuilder,
 };
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use serde_json::to_string_pretty;
+use serde_json::{to_string_pretty, to_value};
 use structopt::StructOpt;
 
 #[derive(StructOpt, Debug)]
@@ -90,7 +91,7 @@ pub enum Command {
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 struct Amount {
-    xch: f64,
+    xch: String,
     mojo: u64,
 }
 
@@ -116,10 +117,10 @@ pub struct Transaction {
 pub struct MemPool {
     height: u64,
     size: usize,
-    cost: f64,
-    fees: f64,
-    additions: f64,
-    removals: f64,
+    cost: u64,
+    fees: Mojos,
+    additions: Mojos,
+    removals: Mojos,
     duration: String,
 }
 
@@ -144,8 +145,11 @@ pub enum GetSubcommand {
         show_parent: bool,
         #[structopt(long = "encode")]
         encode: bool,
-        #[structopt(long = "prefix")]
-        prefix: String,
+        #[structopt(
+            long = "prefix",
+            help = "Address prefix, defaults to the node's network"
+        )]
+        prefix: Option<String>,
     },
     #[structopt(name = "balance")]
     Balance {
@@ -201,12 +205,12 @@ impl Cli {
             .clone()
             .unwrap_or(config_file.cert_path);
 
-        Ok(ClientBuilder::new()
+        ClientBuilder::new()
             .addr(&host, port)
             .key_path(key_path)
             .cert_path(cert_path)
             .build()
-            .await?)
+            .await
     }
 }
 
@@ -215,27 +219,26 @@ impl MemPool {
         Self {
             height,
             size: 0,
-            cost: 0.0,
-            additions: 0.0,
-            removals: 0.0,
-            fees: 0.0,
+            cost: 0,
+            additions: Mojos::ZERO,
+            removals: Mojos::ZERO,
+            fees: Mojos::ZERO,
             duration: String::new(),
         }
     }
 
     pub fn update(&mut self, item: &MemPoolItem) {
         self.size += 1;
-        self.cost += mojo_to_xch(item.cost);
-        self.fees += mojo_to_xch(item.fee);
-        self.additions += mojo_to_xch(item.additions.iter().map(|coin| coin.amount).sum());
-        self.removals += mojo_to_xch(
-            item.removals
-                .clone()
-                .unwrap_or(Vec::new())
-                .iter()
-                .map(|coin| coin.amount)
-                .sum(),
-        );
+        self.cost += item.cost;
+        self.fees += item.fee;
+        self.additions += item.additions.iter().map(|coin| coin.amount).sum();
+        self.removals += item
+            .removals
+            .clone()
+            .unwrap_or_default()
+            .iter()
+            .map(|coin| coin.amount)
+            .sum();
     }
 }
 #[tokio::main]
@@ -272,7 +275,7 @@ async fn main() -> Result<()> {
 async fn get_block(client: &fullnode::Rpc, value: String) -> Result<()> {
     let response = match value.parse::<u64>() {
         Ok(height) => client.get_block_by_height(height).await,
-        Err(_) => client.get_block(&value).await,
+        Err(_) => client.get_block(&value.parse()?).await,
     };
 
     let json = to_string_pretty(&response?)?;
@@ -285,13 +288,19 @@ async fn get_coin(
     value: String,
     show_parent: bool,
     encode: bool,
-    prefix: String,
+    prefix: Option<String>,
 ) -> Result<()> {
-    let response = client.get_coin_record_by_name(&value).await?;
-    let mut coin_record = response.clone();
+    let prefix = match prefix {
+        Some(prefix) => prefix,
+        None if encode => client.get_network().await?.address_prefix.to_string(),
+        None => String::new(),
+    };
+    let response = client.get_coin_record_by_name(&value.parse()?).await?;
+    let mut coin_record = to_value(&response)?;
     if encode {
-        let prefix = "xch";
-        coin_record.coin.puzzle_hash = encode_puzzle_hash(&response.coin.puzzle_hash, prefix)?;
+        coin_record["coin"]["puzzle_hash"] = Address::new(response.coin.puzzle_hash, &prefix)?
+            .to_string()
+            .into();
     }
 
     let json = to_string_pretty(&coin_record)?;
@@ -301,11 +310,13 @@ async fn get_coin(
         let parent_response = client
             .get_coin_record_by_name(&response.coin.parent_coin_info)
             .await?;
-        let mut parent_coin_record = parent_response.clone();
+        let mut parent_coin_record = to_value(&parent_response)?;
 
         if encode {
-            parent_coin_record.coin.puzzle_hash =
-                encode_puzzle_hash(&parent_response.coin.puzzle_hash, &prefix)?;
+            parent_coin_record["coin"]["puzzle_hash"] =
+                Address::new(parent_response.coin.puzzle_hash, &prefix)?
+                    .to_string()
+                    .into();
         }
 
         let parent_json = to_string_pretty(&parent_coin_record)?;
@@ -317,12 +328,13 @@ async fn get_coin(
 }
 
 async fn get_balance(client: &fullnode::Rpc, address: String) -> Result<()> {
-    let puzzle_hash = decode_puzzle_hash(&address)?;
+    let network = client.get_network().await?;
+    let puzzle_hash = network.parse_address(&address)?.puzzle_hash();
     let response = client
         .get_coin_records_by_puzzle_hash(&puzzle_hash, None, None, Some(false))
         .await?;
-    let balance_mojos: u64 = response.iter().map(|record| record.coin.amount).sum();
-    println!("Balance: {:.12} XCH", mojo_to_xch(balance_mojos));
+    let balance_mojos: Mojos = response.iter().map(|record| record.coin.amount).sum();
+    println!("Balance: {:.12}", balance_mojos.display(Unit::Xch));
     Ok(())
 }
 
@@ -356,7 +368,7 @@ async fn get_mempool(client: &fullnode::Rpc, continuous: bool) -> Result<()> {
         let items = client.get_all_mempool_items().await?;
 
         for (key, item) in items.iter() {
-            if seen_items.insert(key.clone()) {
+            if seen_items.insert(*key) {
                 mempool.update(item);
             }
         }
@@ -382,8 +394,8 @@ async fn get_block_count_metrics(client: &fullnode::Rpc) -> Result<()> {
 }
 
 async fn get_transactions(client: &fullnode::Rpc, address: String) -> Result<()> {
-    let puzzle_hash = decode_puzzle_hash(&address)?;
-    let prefix = "xch";
+    let network = client.get_network().await?;
+    let puzzle_hash = network.parse_address(&address)?.puzzle_hash();
     let response = client
         .get_coin_records_by_puzzle_hash(&puzzle_hash, None, None, Some(true))
         .await?;
@@ -395,12 +407,12 @@ async fn get_transactions(client: &fullnode::Rpc, address: String) -> Result<()>
             .await?;
         let amount = Amount {
             xch: mojo_to_xch(record.coin.amount),
-            mojo: record.coin.amount,
+            mojo: record.coin.amount.into(),
         };
         let mut transaction = Transaction {
-            coin: record.coin.parent_coin_info,
+            coin: record.coin.parent_coin_info.to_string(),
             recipient: address.clone(),
-            sender: encode_puzzle_hash(&parent_record.coin.puzzle_hash, prefix)?,
+            sender: network.address(parent_record.coin.puzzle_hash)?.to_string(),
             amount: amount.clone(),
             confirmed_height: record.confirmed_block_index,
             spent_height: record.spent_block_index,
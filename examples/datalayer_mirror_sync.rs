@@ -6,6 +6,7 @@ use std::{
 use anyhow::Result;
 use chia_client::{
     datalayer,
+    types::{Bytes32, Mojos},
     util::{xch_to_mojo, Elapsed},
     wallet, ClientBuilder,
 };
@@ -30,12 +31,12 @@ impl App {
 #[derive(Clone)]
 struct Participant {
     name: String,
-    store_id: String,
+    store_id: Bytes32,
     mirror_url: String,
     clients: Clients,
-    fee: u64,
+    fee: Mojos,
     mirrors: Vec<String>,
-    subscriptions: Vec<String>,
+    subscriptions: Vec<Bytes32>,
 }
 
 #[derive(Clone)]
@@ -52,12 +53,11 @@ async fn main() -> Result<()> {
         .init();
 
     let mut app = App::new();
-    let fee = xch_to_mojo(0.0000005);
+    let fee = xch_to_mojo("0.0000005")?;
 
     let alice = Participant {
         name: "Alice".to_string(),
-        store_id: "923bf39ca5511fad813680943773d597c3c1e3608e4fcc93d2bff600e0bcf937".to_string(),
-        mirror_url: "http://tun.hashlink.ch:9090".to_string(),
+        store_id: "923bf39ca5511fad813680943773d597c3c1e3608e4fcc93d2bff600e0bcf937".parse()?,
         mirror_url: "http://chia-node1:8575".to_string(),
         clients: Clients {
             wallet: setup_wallet("node1", "127.0.0.1", 9250).await?,
@@ -72,7 +72,7 @@ async fn main() -> Result<()> {
 
     let bob = Participant {
         name: "Bob".to_string(),
-        store_id: "05915a7ec111ff8070d380e71654d74d5e95418646b264eb2b94eca3541dfe54".to_string(),
+        store_id: "05915a7ec111ff8070d380e71654d74d5e95418646b264eb2b94eca3541dfe54".parse()?,
         mirror_url: "http://chia-node2:8575".to_string(),
         clients: Clients {
             wallet: setup_wallet("node2", "127.0.0.1", 9251).await?,
@@ -90,7 +90,7 @@ async fn main() -> Result<()> {
     );
     for i in 0..app.participants.len() {
         let participant = &mut app.participants[i];
-        if participant.store_id.is_empty() {
+        if participant.store_id == Bytes32::default() {
             participant.store_id = match participant
                 .clients
                 .datalayer
@@ -109,11 +109,11 @@ async fn main() -> Result<()> {
     info!("Checking if participants have enough balance to run all operations.");
     for participant in &app.participants {
         let balance = participant.clients.wallet.get_wallet_balance(1).await?;
-        if balance.spendable_balance < (participant.fee * 3) as i64 {
+        if balance.spendable_balance < Mojos(participant.fee.0 * 3) {
             error!(
                 "{}'s balance is too low. Please top up the wallet with at least {} mojos",
                 participant.name,
-                participant.fee * 3
+                participant.fee.0 * 3
             );
         }
     }
@@ -160,7 +160,7 @@ async fn main() -> Result<()> {
                     .await?;
 
                 // Collect new subscription
-                new_subs.push(app.participants[j].store_id.clone());
+                new_subs.push(app.participants[j].store_id);
             }
         }
 
@@ -200,7 +200,7 @@ async fn main() -> Result<()> {
                 .add_mirror(
                     &participant.store_id,
                     vec![&participant.mirror_url],
-                    0,
+                    Mojos::ZERO,
                     participant.fee,
                 )
                 .await?;
@@ -215,7 +215,7 @@ async fn main() -> Result<()> {
         //let value = "fadedcab";
         let single_char = 'a';
         let size = 20 * 1_000_000;
-        let value: String = std::iter::repeat(single_char).take(size).collect();
+        let value: String = std::iter::repeat_n(single_char, size).collect();
         let tx_id = participant
             .clients
             .datalayer
@@ -230,7 +230,7 @@ async fn main() -> Result<()> {
         wait_for_confirmation(&participant.clients.wallet, &tx_id).await?;
         info!("Wrote data successfully with TX: {tx_id}");
 
-        for (_, (other_clients, other_name)) in participant_info.iter().enumerate() {
+        for (other_clients, other_name) in participant_info.iter() {
             wait_for_sync(
                 &participant.name,
                 &other_clients.datalayer,
@@ -248,7 +248,7 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn wait_for_confirmation(client: &wallet::Rpc, tx_id: &str) -> Result<()> {
+async fn wait_for_confirmation(client: &wallet::Rpc, tx_id: &Bytes32) -> Result<()> {
     let started = Instant::now();
     info!("Checking transaction status of tx: {tx_id}");
     loop {
@@ -269,7 +269,7 @@ async fn wait_for_confirmation(client: &wallet::Rpc, tx_id: &str) -> Result<()>
     Ok(())
 }
 
-async fn wait_for_sync(name: &str, client: &datalayer::Rpc, store_id: &str) -> Result<()> {
+async fn wait_for_sync(name: &str, client: &datalayer::Rpc, store_id: &Bytes32) -> Result<()> {
     let started = Instant::now();
     info!("Checking sync status of {name}'s DataLayer Client of store_id: {store_id}");
     loop {
@@ -285,13 +285,13 @@ async fn wait_for_sync(name: &str, client: &datalayer::Rpc, store_id: &str) -> R
     Ok(())
 }
 
-fn generate_key(store_id: &str) -> String {
+fn generate_key(store_id: &Bytes32) -> String {
     let start_of_key = "0x";
     let now = SystemTime::now();
     let since_the_epoch = now.duration_since(UNIX_EPOCH).expect("Time went backwards");
     let timestamp = since_the_epoch.as_secs();
 
-    let hashed_store_id = format!("{:x}", hash(store_id));
+    let hashed_store_id = format!("{:x}", hash(&store_id.to_string()));
     format!("{}{}{:x}", start_of_key, hashed_store_id, timestamp)
 }
 
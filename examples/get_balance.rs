@@ -3,7 +3,7 @@ use std::path::Path;
 use anyhow::Result;
 use chia_client::{
     fullnode,
-    util::{decode_puzzle_hash, mojo_to_xch},
+    types::{Address, Mojos, Unit},
     ClientBuilder,
 };
 
@@ -25,11 +25,11 @@ async fn main() -> Result<()> {
 
     let address = "xch10n6l66hhx3qrx2ttdvaj54mmy2u63jvhzalj5t6d89npsl4psmvqtsq8fz";
     //Insert your wallet address
-    let puzzle_hash = decode_puzzle_hash(address)?;
+    let puzzle_hash = address.parse::<Address>()?.puzzle_hash();
     let response = node
         .get_coin_records_by_puzzle_hash(&puzzle_hash, None, None, Some(false))
         .await?;
-    let balance_mojos: u64 = response.iter().map(|record| record.coin.amount).sum();
-    println!("Balance: {:.12} XCH", mojo_to_xch(balance_mojos));
+    let balance_mojos: Mojos = response.iter().map(|record| record.coin.amount).sum();
+    println!("Balance: {:.12}", balance_mojos.display(Unit::Xch));
     Ok(())
 }
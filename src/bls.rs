@@ -0,0 +1,344 @@
+//! Offline BLS signature checks: `AugSchemeMPL` verification, CHIP-0002 signed messages, and
+//! the standard puzzle hash a public key controls.
+
+use bls12_381::{
+    hash_to_curve::{ExpandMessageState, HashToCurve, InitExpandMessage},
+    pairing, G1Affine, G1Projective, G2Affine, G2Projective, Scalar,
+};
+use hex_literal::hex;
+use num_bigint::{BigInt, Sign};
+use num_integer::Integer;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    clvm::{curry_tree_hash, Program, GROUP_ORDER},
+    models::wallet::SigningMode,
+    puzzles::STANDARD_PUZZLE_HASH,
+    types::{Address, Bytes32, PuzzleHash},
+    util::decode_hex,
+    Error,
+};
+
+/// The domain separation tag of Chia's `AugSchemeMPL`.
+pub const AUG_SCHEME_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG_";
+/// What CHIP-0002 pairs a message with before signing its tree hash, so signed messages can
+/// never be valid spend signatures.
+pub const CHIP_0002_SIGN_MESSAGE_PREFIX: &str = "Chia Signed Message";
+/// The hidden puzzle of standard wallets, `(=)`, which makes the hidden spend path unusable.
+pub const DEFAULT_HIDDEN_PUZZLE_HASH: Bytes32 = Bytes32::new(hex!(
+    "711d6c4e32c92e53179b199484cf8c897542bc57f2b22582799f9d657eec4699"
+));
+
+/// Verifies an `AugSchemeMPL` signature, where the signed message is the public key followed
+/// by `message`. Keys and signatures are compressed points.
+pub fn verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> Result<bool, Error> {
+    let public_key = parse_public_key(public_key)?;
+    let signature = parse_signature(signature)?;
+    let mut augmented = public_key.to_compressed().to_vec();
+    augmented.extend_from_slice(message);
+    let hashed = hash_to_g2(&augmented, AUG_SCHEME_DST);
+    Ok(
+        pairing(&public_key, &G2Affine::from(hashed))
+            == pairing(&G1Affine::generator(), &signature),
+    )
+}
+
+/// Hashes a message to G1 with `expand_message_xmd` over SHA-256, as in RFC 9380.
+pub fn hash_to_g1(message: &[u8], dst: &[u8]) -> G1Projective {
+    <G1Projective as HashToCurve<ExpandMsgXmdSha256>>::hash_to_curve(message, dst)
+}
+
+/// Hashes a message to G2 with `expand_message_xmd` over SHA-256, as in RFC 9380.
+pub fn hash_to_g2(message: &[u8], dst: &[u8]) -> G2Projective {
+    <G2Projective as HashToCurve<ExpandMsgXmdSha256>>::hash_to_curve(message, dst)
+}
+
+/// `expand_message_xmd` with SHA-256. `bls12_381` ships one built on an older `digest` than
+/// the `sha2` this crate uses, so it is implemented here.
+struct ExpandMsgXmdSha256;
+
+struct ExpandedBytes {
+    bytes: Vec<u8>,
+    offset: usize,
+}
+
+impl<'x> InitExpandMessage<'x> for ExpandMsgXmdSha256 {
+    type Expander = ExpandedBytes;
+
+    fn init_expand(message: &[u8], dst: &'x [u8], len_in_bytes: usize) -> ExpandedBytes {
+        const OVERSIZE_DST_PREFIX: &[u8] = b"H2C-OVERSIZE-DST-";
+        let dst = match dst.len() {
+            0..=255 => dst.to_vec(),
+            _ => Sha256::new()
+                .chain_update(OVERSIZE_DST_PREFIX)
+                .chain_update(dst)
+                .finalize()
+                .to_vec(),
+        };
+        let ell = len_in_bytes.div_ceil(32);
+        assert!(
+            ell <= 255 && len_in_bytes <= 0xffff,
+            "expand_message_xmd output too long"
+        );
+        let dst_prime = [dst.as_slice(), &[dst.len() as u8]].concat();
+
+        let b_0 = Sha256::new()
+            .chain_update([0u8; 64])
+            .chain_update(message)
+            .chain_update((len_in_bytes as u16).to_be_bytes())
+            .chain_update([0u8])
+            .chain_update(&dst_prime)
+            .finalize();
+        let mut b_i = Sha256::new()
+            .chain_update(b_0)
+            .chain_update([1u8])
+            .chain_update(&dst_prime)
+            .finalize();
+        let mut bytes = b_i.to_vec();
+        for i in 2..=ell {
+            let mixed: Vec<u8> = b_0.iter().zip(b_i.iter()).map(|(a, b)| a ^ b).collect();
+            b_i = Sha256::new()
+                .chain_update(mixed)
+                .chain_update([i as u8])
+                .chain_update(&dst_prime)
+                .finalize();
+            bytes.extend_from_slice(&b_i);
+        }
+        bytes.truncate(len_in_bytes);
+        ExpandedBytes { bytes, offset: 0 }
+    }
+}
+
+impl ExpandMessageState<'_> for ExpandedBytes {
+    fn read_into(&mut self, output: &mut [u8]) -> usize {
+        let len = output.len().min(self.remain());
+        output[..len].copy_from_slice(&self.bytes[self.offset..self.offset + len]);
+        self.offset += len;
+        len
+    }
+
+    fn remain(&self) -> usize {
+        self.bytes.len() - self.offset
+    }
+}
+
+/// The bytes a wallet signs for `message` in the given mode: for the CHIP-0002 modes, the tree
+/// hash of `("Chia Signed Message" . message)`.
+pub fn signed_message(message: &str, mode: SigningMode) -> Result<Vec<u8>, Error> {
+    let chip_0002 = |message: Vec<u8>| {
+        let pair = Program::cons(
+            Program::atom(CHIP_0002_SIGN_MESSAGE_PREFIX.as_bytes()),
+            Program::atom(message),
+        );
+        pair.tree_hash().to_bytes().to_vec()
+    };
+    Ok(match mode {
+        SigningMode::Chip0002 => chip_0002(message.as_bytes().to_vec()),
+        SigningMode::Chip0002HexInput => chip_0002(decode_hex(message)?),
+        SigningMode::Utf8Input => message.as_bytes().to_vec(),
+        SigningMode::HexInput => decode_hex(message)?,
+    })
+}
+
+/// Verifies a message signed by `sign_message_by_address` or `sign_message_by_id`, with the
+/// public key and signature as hex.
+pub fn verify_signed_message(
+    public_key: &str,
+    message: &str,
+    signature: &str,
+    mode: SigningMode,
+) -> Result<bool, Error> {
+    verify(
+        &decode_hex(public_key)?,
+        &signed_message(message, mode)?,
+        &decode_hex(signature)?,
+    )
+}
+
+/// Like [`verify_signed_message`], but also requires the key to control `address` through the
+/// standard puzzle, which is what proves the signer owns the address. `sign_message_by_address`
+/// signs with, and returns, the synthetic key curried into the puzzle, so that is the key
+/// expected here.
+pub fn verify_signed_message_for_address(
+    public_key: &str,
+    message: &str,
+    signature: &str,
+    mode: SigningMode,
+    address: &Address,
+) -> Result<bool, Error> {
+    let synthetic_key = decode_hex(public_key)?;
+    if standard_puzzle_hash_for_synthetic_key(&synthetic_key)? != address.puzzle_hash() {
+        return Ok(false);
+    }
+    verify_signed_message(public_key, message, signature, mode)
+}
+
+/// Offsets a public key by `sha256(public_key + hidden_puzzle_hash)`, giving the key curried
+/// into the standard puzzle.
+pub fn synthetic_public_key(
+    public_key: &[u8],
+    hidden_puzzle_hash: &Bytes32,
+) -> Result<[u8; 48], Error> {
+    let point = parse_public_key(public_key)?;
+    let mut hasher = Sha256::new();
+    hasher.update(point.to_compressed());
+    hasher.update(hidden_puzzle_hash.as_bytes());
+    let offset = BigInt::from_signed_bytes_be(&hasher.finalize())
+        .mod_floor(&BigInt::from_bytes_be(Sign::Plus, &GROUP_ORDER));
+    let (_, mut bytes) = offset.to_bytes_le();
+    bytes.resize(32, 0);
+    let scalar = Scalar::from_bytes(&bytes.try_into().expect("32 bytes")).expect("reduced scalar");
+    let synthetic = G1Projective::from(&point) + G1Projective::generator() * scalar;
+    Ok(G1Affine::from(synthetic).to_compressed())
+}
+
+/// The puzzle hash of the standard puzzle for a wallet public key, i.e. what its address
+/// encodes.
+pub fn standard_puzzle_hash(public_key: &[u8]) -> Result<PuzzleHash, Error> {
+    let synthetic_key = synthetic_public_key(public_key, &DEFAULT_HIDDEN_PUZZLE_HASH)?;
+    standard_puzzle_hash_for_synthetic_key(&synthetic_key)
+}
+
+/// The puzzle hash of the standard puzzle with `synthetic_key` curried in, for keys that are
+/// already offset by [`synthetic_public_key`].
+pub fn standard_puzzle_hash_for_synthetic_key(synthetic_key: &[u8]) -> Result<PuzzleHash, Error> {
+    parse_public_key(synthetic_key)?;
+    let key_hash = Program::atom(synthetic_key).tree_hash();
+    Ok(curry_tree_hash(&STANDARD_PUZZLE_HASH, &[key_hash]).into())
+}
+
+fn parse_public_key(bytes: &[u8]) -> Result<G1Affine, Error> {
+    let bytes: &[u8; 48] = bytes.try_into().map_err(|_| {
+        Error::BlsError(format!("public key has {} bytes, expected 48", bytes.len()))
+    })?;
+    Option::<G1Affine>::from(G1Affine::from_compressed(bytes))
+        .filter(|point| !bool::from(point.is_identity()))
+        .ok_or_else(|| Error::BlsError("invalid public key".to_string()))
+}
+
+fn parse_signature(bytes: &[u8]) -> Result<G2Affine, Error> {
+    let bytes: &[u8; 96] = bytes.try_into().map_err(|_| {
+        Error::BlsError(format!("signature has {} bytes, expected 96", bytes.len()))
+    })?;
+    Option::<G2Affine>::from(G2Affine::from_compressed(bytes))
+        .ok_or_else(|| Error::BlsError("invalid signature".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use super::*;
+
+    /// A key derived for these tests, with its synthetic key, puzzle hash and address computed
+    /// independently from the serialized standard puzzle.
+    const SECRET_KEY: [u8; 32] =
+        hex!("34e4496fdcd263d2b1513b1a6e1bcdd7dd4f7fe8d65f64b6f941bb8da80c2c70");
+    const PUBLIC_KEY: [u8; 48] = hex!(
+        "8c2948201452d3c90ab9adf7216bbd6525816242d77b988e67f40f79fbd0c045a6ed039d06241e54c9fd4425adf75ef2"
+    );
+    const SYNTHETIC_SECRET_KEY: [u8; 32] =
+        hex!("0e08d37e41677163dd16bc72ab27b1fcadcc79fcc7227dfabbab1345a89f85e8");
+    const SYNTHETIC_PUBLIC_KEY: [u8; 48] = hex!(
+        "82fb757f7336996616133a82ac32b0543f3312535ff4dfd25ff81ce7303e496fa6e8f991554a1cc818045f11fcaa2c0d"
+    );
+    const PUZZLE_HASH: [u8; 32] =
+        hex!("8908e92d96fcc7ad517a00ee023ea9aece9bfa28ba3ec11de812268796c33617");
+    const ADDRESS: &str = "xch13yywjtvklnr665t6qrhqy04f4m8fh73ghglvz80gzgng09krxcts3jzqje";
+
+    fn scalar(big_endian: [u8; 32]) -> Scalar {
+        let mut little_endian = big_endian;
+        little_endian.reverse();
+        Scalar::from_bytes(&little_endian).unwrap()
+    }
+
+    fn sign(secret_key: [u8; 32], message: &[u8]) -> [u8; 96] {
+        let public_key = G1Affine::from(G1Projective::generator() * scalar(secret_key));
+        let augmented = [public_key.to_compressed().as_slice(), message].concat();
+        G2Affine::from(hash_to_g2(&augmented, AUG_SCHEME_DST) * scalar(secret_key)).to_compressed()
+    }
+
+    #[test]
+    fn expand_message_xmd_matches_rfc_9380() {
+        const DST: &[u8] = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        let cases: [(&[u8], &str); 4] = [
+            (
+                b"",
+                "68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235",
+            ),
+            (
+                b"abc",
+                "d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615",
+            ),
+            (
+                b"abcdef0123456789",
+                "eff31487c770a893cfb36f912fbfcbff40d5661771ca4b2cb4eafe524333f5c1",
+            ),
+            (
+                b"",
+                "af84c27ccfd45d41914fdff5df25293e221afc53d8ad2ac06d5e3e29485dadbee0d121587713a3e0\
+                 dd4d5e69e93eb7cd4f5df4cd103e188cf60cb02edc3edf18eda8576c412b18ffb658e3dd6ec84946\
+                 9b979d444cf7b26911a08e63cf31f9dcc541708d3491184472c2c29bb749d4286b004ceb5ee6b9a7\
+                 fa5b646c993f0ced",
+            ),
+        ];
+        for (message, expected) in cases {
+            let expected = hex::decode(expected).unwrap();
+            let mut expander = ExpandMsgXmdSha256::init_expand(message, DST, expected.len());
+            let mut output = vec![0; expected.len()];
+            assert_eq!(expander.read_into(&mut output), expected.len());
+            assert_eq!(output, expected);
+        }
+    }
+
+    #[test]
+    fn standard_puzzle_hash_of_wallet_key() {
+        assert_eq!(
+            synthetic_public_key(&PUBLIC_KEY, &DEFAULT_HIDDEN_PUZZLE_HASH).unwrap(),
+            SYNTHETIC_PUBLIC_KEY
+        );
+        let puzzle_hash = PuzzleHash::new(PUZZLE_HASH);
+        assert_eq!(standard_puzzle_hash(&PUBLIC_KEY).unwrap(), puzzle_hash);
+        assert_eq!(
+            standard_puzzle_hash_for_synthetic_key(&SYNTHETIC_PUBLIC_KEY).unwrap(),
+            puzzle_hash
+        );
+        assert_eq!(
+            Address::new(puzzle_hash, "xch").unwrap().to_string(),
+            ADDRESS
+        );
+    }
+
+    #[test]
+    fn verify_message_signed_by_address() {
+        let address = Address::from_str(ADDRESS).unwrap();
+        let message = "I own this address";
+        let signed = signed_message(message, SigningMode::Chip0002).unwrap();
+        let verify = |public_key: &[u8], message: &str, secret_key: [u8; 32]| {
+            verify_signed_message_for_address(
+                &hex::encode(public_key),
+                message,
+                &hex::encode(sign(secret_key, &signed)),
+                SigningMode::Chip0002,
+                &address,
+            )
+            .unwrap()
+        };
+
+        assert!(verify(&SYNTHETIC_PUBLIC_KEY, message, SYNTHETIC_SECRET_KEY));
+        assert!(!verify(
+            &SYNTHETIC_PUBLIC_KEY,
+            "I own another address",
+            SYNTHETIC_SECRET_KEY
+        ));
+        // A valid signature by the master key does not prove ownership of the address.
+        assert!(verify_signed_message(
+            &hex::encode(PUBLIC_KEY),
+            message,
+            &hex::encode(sign(SECRET_KEY, &signed)),
+            SigningMode::Chip0002,
+        )
+        .unwrap());
+        assert!(!verify(&PUBLIC_KEY, message, SECRET_KEY));
+    }
+}
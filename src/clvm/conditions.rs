@@ -0,0 +1,552 @@
+use super::{atom_to_bigint, bigint_to_atom, run::amount_is_invalid, Program};
+use crate::{
+    types::{Bytes32, CoinId, Mojos, PuzzleHash},
+    Error,
+};
+
+/// The cost each `CREATE_COIN` condition adds to a spend.
+pub const CREATE_COIN_COST: u64 = 1_800_000;
+const AGG_SIG_COST: u64 = 1_200_000;
+const SOFTFORK_COST_FACTOR: u64 = 10_000;
+
+/// A condition returned by a puzzle, as defined in CHIP-0011 and its successors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpendCondition {
+    Remark(Vec<Program>),
+    AggSigParent {
+        public_key: Vec<u8>,
+        message: Vec<u8>,
+    },
+    AggSigPuzzle {
+        public_key: Vec<u8>,
+        message: Vec<u8>,
+    },
+    AggSigAmount {
+        public_key: Vec<u8>,
+        message: Vec<u8>,
+    },
+    AggSigPuzzleAmount {
+        public_key: Vec<u8>,
+        message: Vec<u8>,
+    },
+    AggSigParentAmount {
+        public_key: Vec<u8>,
+        message: Vec<u8>,
+    },
+    AggSigParentPuzzle {
+        public_key: Vec<u8>,
+        message: Vec<u8>,
+    },
+    AggSigUnsafe {
+        public_key: Vec<u8>,
+        message: Vec<u8>,
+    },
+    AggSigMe {
+        public_key: Vec<u8>,
+        message: Vec<u8>,
+    },
+    CreateCoin {
+        puzzle_hash: PuzzleHash,
+        amount: Mojos,
+        memos: Vec<Vec<u8>>,
+    },
+    ReserveFee {
+        amount: Mojos,
+    },
+    CreateCoinAnnouncement {
+        message: Vec<u8>,
+    },
+    AssertCoinAnnouncement {
+        announcement_id: Bytes32,
+    },
+    CreatePuzzleAnnouncement {
+        message: Vec<u8>,
+    },
+    AssertPuzzleAnnouncement {
+        announcement_id: Bytes32,
+    },
+    AssertConcurrentSpend {
+        coin_id: CoinId,
+    },
+    AssertConcurrentPuzzle {
+        puzzle_hash: PuzzleHash,
+    },
+    SendMessage {
+        mode: u8,
+        message: Vec<u8>,
+        args: Vec<Program>,
+    },
+    ReceiveMessage {
+        mode: u8,
+        message: Vec<u8>,
+        args: Vec<Program>,
+    },
+    AssertMyCoinId {
+        coin_id: CoinId,
+    },
+    AssertMyParentId {
+        parent_id: CoinId,
+    },
+    AssertMyPuzzleHash {
+        puzzle_hash: PuzzleHash,
+    },
+    AssertMyAmount {
+        amount: Mojos,
+    },
+    AssertMyBirthSeconds {
+        seconds: u64,
+    },
+    AssertMyBirthHeight {
+        height: u32,
+    },
+    AssertEphemeral,
+    AssertSecondsRelative {
+        seconds: u64,
+    },
+    AssertSecondsAbsolute {
+        seconds: u64,
+    },
+    AssertHeightRelative {
+        height: u32,
+    },
+    AssertHeightAbsolute {
+        height: u32,
+    },
+    AssertBeforeSecondsRelative {
+        seconds: u64,
+    },
+    AssertBeforeSecondsAbsolute {
+        seconds: u64,
+    },
+    AssertBeforeHeightRelative {
+        height: u32,
+    },
+    AssertBeforeHeightAbsolute {
+        height: u32,
+    },
+    Softfork {
+        cost: u64,
+        args: Vec<Program>,
+    },
+    /// An opcode this client does not know; the mempool accepts and ignores these.
+    Unknown {
+        opcode: Vec<u8>,
+        args: Vec<Program>,
+    },
+}
+
+impl SpendCondition {
+    /// Parses a single `(opcode arg1 arg2 ...)` condition.
+    pub fn parse(condition: &Program) -> Result<SpendCondition, Error> {
+        let items = condition
+            .to_list()
+            .filter(|items| !items.is_empty())
+            .ok_or_else(|| invalid(condition, "condition must be a non-empty list"))?;
+        let opcode = items[0]
+            .as_atom()
+            .ok_or_else(|| invalid(condition, "opcode must be an atom"))?;
+        let args = &items[1..];
+        let arg = |i: usize| -> Result<&Program, Error> {
+            args.get(i)
+                .copied()
+                .ok_or_else(|| invalid(condition, "missing argument"))
+        };
+        let bytes = |i: usize| -> Result<Vec<u8>, Error> {
+            arg(i)?
+                .as_atom()
+                .map(<[u8]>::to_vec)
+                .ok_or_else(|| invalid(condition, "argument must be an atom"))
+        };
+        let hash = |i: usize| -> Result<Bytes32, Error> {
+            Bytes32::try_from(bytes(i)?.as_slice())
+                .map_err(|_| invalid(condition, "argument must be 32 bytes"))
+        };
+        let number = |i: usize| -> Result<u64, Error> {
+            let atom = bytes(i)?;
+            if amount_is_invalid(&atom) {
+                return Err(invalid(
+                    condition,
+                    "argument must be a canonical unsigned integer",
+                ));
+            }
+            Ok(arg(i)?.as_u64().expect("valid amount"))
+        };
+        let height = |i: usize| -> Result<u32, Error> {
+            u32::try_from(number(i)?).map_err(|_| invalid(condition, "height must fit in u32"))
+        };
+        // Timelocks are sanitized like consensus does: an argument below zero or above `max`
+        // makes the assertion always or never true instead of invalidating the spend.
+        let timelock = |i: usize, max: u64| -> Result<Timelock, Error> {
+            let atom = bytes(i)?;
+            if bigint_to_atom(&atom_to_bigint(&atom)) != atom {
+                return Err(invalid(condition, "argument must be a canonical integer"));
+            }
+            if atom.first().is_some_and(|b| b & 0x80 != 0) {
+                return Ok(Timelock::Negative);
+            }
+            let digits = atom.strip_prefix(&[0]).unwrap_or(&atom);
+            if digits.len() > 8 {
+                return Ok(Timelock::TooLarge);
+            }
+            let value = digits.iter().fold(0, |value, b| value << 8 | u64::from(*b));
+            Ok(if value > max {
+                Timelock::TooLarge
+            } else {
+                Timelock::Value(value)
+            })
+        };
+        let impossible = || invalid(condition, "assertion can never be satisfied");
+        // ASSERT_SECONDS_* and ASSERT_HEIGHT_*: a negative time has always passed.
+        let after = |i: usize, max: u64| -> Result<u64, Error> {
+            match timelock(i, max)? {
+                Timelock::Negative => Ok(0),
+                Timelock::TooLarge => Err(impossible()),
+                Timelock::Value(value) => Ok(value),
+            }
+        };
+        // ASSERT_BEFORE_*: a time past `max` is never reached.
+        let before = |i: usize, max: u64| -> Result<u64, Error> {
+            match timelock(i, max)? {
+                Timelock::Negative => Err(impossible()),
+                Timelock::TooLarge => Ok(max),
+                Timelock::Value(value) => Ok(value),
+            }
+        };
+        let (seconds_max, height_max) = (u64::MAX, u64::from(u32::MAX));
+        let agg_sig = || -> Result<(Vec<u8>, Vec<u8>), Error> {
+            let public_key = bytes(0)?;
+            if public_key.len() != 48 {
+                return Err(invalid(condition, "public key must be 48 bytes"));
+            }
+            Ok((public_key, bytes(1)?))
+        };
+        let message = |mode_index: usize| -> Result<(u8, Vec<u8>, Vec<Program>), Error> {
+            let mode = u8::try_from(number(mode_index)?)
+                .map_err(|_| invalid(condition, "message mode must fit in u8"))?;
+            let message = bytes(mode_index + 1)?;
+            let rest = args[mode_index + 2..]
+                .iter()
+                .map(|a| (*a).clone())
+                .collect();
+            Ok((mode, message, rest))
+        };
+        let rest = |from: usize| args.iter().skip(from).map(|a| (*a).clone()).collect();
+
+        let [opcode] = opcode else {
+            return Ok(SpendCondition::Unknown {
+                opcode: opcode.to_vec(),
+                args: rest(0),
+            });
+        };
+        Ok(match opcode {
+            1 => SpendCondition::Remark(rest(0)),
+            43..=50 => {
+                let (public_key, message) = agg_sig()?;
+                match opcode {
+                    43 => SpendCondition::AggSigParent {
+                        public_key,
+                        message,
+                    },
+                    44 => SpendCondition::AggSigPuzzle {
+                        public_key,
+                        message,
+                    },
+                    45 => SpendCondition::AggSigAmount {
+                        public_key,
+                        message,
+                    },
+                    46 => SpendCondition::AggSigPuzzleAmount {
+                        public_key,
+                        message,
+                    },
+                    47 => SpendCondition::AggSigParentAmount {
+                        public_key,
+                        message,
+                    },
+                    48 => SpendCondition::AggSigParentPuzzle {
+                        public_key,
+                        message,
+                    },
+                    49 => SpendCondition::AggSigUnsafe {
+                        public_key,
+                        message,
+                    },
+                    _ => SpendCondition::AggSigMe {
+                        public_key,
+                        message,
+                    },
+                }
+            },
+            51 => {
+                let memos = match args.get(2) {
+                    Some(memos) if memos.is_pair() => memos
+                        .iter()
+                        .filter_map(|memo| memo.as_atom().map(<[u8]>::to_vec))
+                        .collect(),
+                    _ => Vec::new(),
+                };
+                SpendCondition::CreateCoin {
+                    puzzle_hash: hash(0)?.into(),
+                    amount: Mojos(number(1)?),
+                    memos,
+                }
+            },
+            52 => SpendCondition::ReserveFee {
+                amount: Mojos(number(0)?),
+            },
+            60 => SpendCondition::CreateCoinAnnouncement { message: bytes(0)? },
+            61 => SpendCondition::AssertCoinAnnouncement {
+                announcement_id: hash(0)?,
+            },
+            62 => SpendCondition::CreatePuzzleAnnouncement { message: bytes(0)? },
+            63 => SpendCondition::AssertPuzzleAnnouncement {
+                announcement_id: hash(0)?,
+            },
+            64 => SpendCondition::AssertConcurrentSpend {
+                coin_id: hash(0)?.into(),
+            },
+            65 => SpendCondition::AssertConcurrentPuzzle {
+                puzzle_hash: hash(0)?.into(),
+            },
+            66 => {
+                let (mode, message, args) = message(0)?;
+                SpendCondition::SendMessage {
+                    mode,
+                    message,
+                    args,
+                }
+            },
+            67 => {
+                let (mode, message, args) = message(0)?;
+                SpendCondition::ReceiveMessage {
+                    mode,
+                    message,
+                    args,
+                }
+            },
+            70 => SpendCondition::AssertMyCoinId {
+                coin_id: hash(0)?.into(),
+            },
+            71 => SpendCondition::AssertMyParentId {
+                parent_id: hash(0)?.into(),
+            },
+            72 => SpendCondition::AssertMyPuzzleHash {
+                puzzle_hash: hash(0)?.into(),
+            },
+            73 => SpendCondition::AssertMyAmount {
+                amount: Mojos(number(0)?),
+            },
+            74 => SpendCondition::AssertMyBirthSeconds {
+                seconds: number(0)?,
+            },
+            75 => SpendCondition::AssertMyBirthHeight { height: height(0)? },
+            76 => SpendCondition::AssertEphemeral,
+            80 => SpendCondition::AssertSecondsRelative {
+                seconds: after(0, seconds_max)?,
+            },
+            81 => SpendCondition::AssertSecondsAbsolute {
+                seconds: after(0, seconds_max)?,
+            },
+            82 => SpendCondition::AssertHeightRelative {
+                height: after(0, height_max)? as u32,
+            },
+            83 => SpendCondition::AssertHeightAbsolute {
+                height: after(0, height_max)? as u32,
+            },
+            84 => SpendCondition::AssertBeforeSecondsRelative {
+                seconds: before(0, seconds_max)?,
+            },
+            85 => SpendCondition::AssertBeforeSecondsAbsolute {
+                seconds: before(0, seconds_max)?,
+            },
+            86 => SpendCondition::AssertBeforeHeightRelative {
+                height: before(0, height_max)? as u32,
+            },
+            87 => SpendCondition::AssertBeforeHeightAbsolute {
+                height: before(0, height_max)? as u32,
+            },
+            90 => SpendCondition::Softfork {
+                cost: number(0)?,
+                args: rest(1),
+            },
+            _ => SpendCondition::Unknown {
+                opcode: vec![*opcode],
+                args: rest(0),
+            },
+        })
+    }
+
+    /// The cost the mempool charges for the condition on top of the CLVM cost.
+    pub fn cost(&self) -> u64 {
+        match self {
+            SpendCondition::CreateCoin { .. } => CREATE_COIN_COST,
+            SpendCondition::AggSigParent { .. }
+            | SpendCondition::AggSigPuzzle { .. }
+            | SpendCondition::AggSigAmount { .. }
+            | SpendCondition::AggSigPuzzleAmount { .. }
+            | SpendCondition::AggSigParentAmount { .. }
+            | SpendCondition::AggSigParentPuzzle { .. }
+            | SpendCondition::AggSigUnsafe { .. }
+            | SpendCondition::AggSigMe { .. } => AGG_SIG_COST,
+            SpendCondition::Softfork { cost, .. } => cost.saturating_mul(SOFTFORK_COST_FACTOR),
+            _ => 0,
+        }
+    }
+}
+
+/// A timelock argument before it is clamped to its type.
+enum Timelock {
+    Negative,
+    TooLarge,
+    Value(u64),
+}
+
+fn invalid(condition: &Program, reason: &str) -> Error {
+    Error::ClvmError(format!("invalid condition {}: {}", condition, reason))
+}
+
+/// The outcome of running a coin spend: its conditions and what they cost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpendConditions {
+    pub coin_id: CoinId,
+    pub clvm_cost: u64,
+    pub conditions: Vec<SpendCondition>,
+}
+
+impl SpendConditions {
+    pub fn condition_cost(&self) -> u64 {
+        self.conditions.iter().map(SpendCondition::cost).sum()
+    }
+
+    /// CLVM plus condition cost. Block generators are also charged per byte, which is not
+    /// included here.
+    pub fn total_cost(&self) -> u64 {
+        self.clvm_cost + self.condition_cost()
+    }
+
+    /// The coins the spend creates, as `(puzzle_hash, amount)` pairs.
+    pub fn created_coins(&self) -> impl Iterator<Item = (PuzzleHash, Mojos)> + '_ {
+        self.conditions
+            .iter()
+            .filter_map(|condition| match condition {
+                SpendCondition::CreateCoin {
+                    puzzle_hash,
+                    amount,
+                    ..
+                } => Some((*puzzle_hash, *amount)),
+                _ => None,
+            })
+    }
+
+    /// The total of the spend's `RESERVE_FEE` conditions, or `None` if it overflows a `u64`,
+    /// which consensus rejects.
+    pub fn reserved_fee(&self) -> Option<Mojos> {
+        self.conditions
+            .iter()
+            .filter_map(|condition| match condition {
+                SpendCondition::ReserveFee { amount } => Some(*amount),
+                _ => None,
+            })
+            .try_fold(Mojos::ZERO, Mojos::checked_add)
+    }
+}
+
+/// Runs `puzzle` with `solution` and parses its output as a list of conditions.
+pub fn run_puzzle(
+    coin_id: CoinId,
+    puzzle: &Program,
+    solution: &Program,
+    max_cost: u64,
+) -> Result<SpendConditions, Error> {
+    let (clvm_cost, output) = puzzle.run(solution, max_cost)?;
+    let conditions = output
+        .to_list()
+        .ok_or_else(|| Error::ClvmError(format!("puzzle output is not a list: {}", output)))?
+        .into_iter()
+        .map(SpendCondition::parse)
+        .collect::<Result<Vec<SpendCondition>, Error>>()?;
+    Ok(SpendConditions {
+        coin_id,
+        clvm_cost,
+        conditions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clvm::MAX_BLOCK_COST_CLVM;
+
+    #[test]
+    fn run_puzzle_parses_conditions_and_costs() {
+        let puzzle_hash = PuzzleHash::new([0x11; 32]);
+        let puzzle = Program::assemble(&format!(
+            "(q . ((51 0x{} 1000) (52 10) (1 \"note\")))",
+            hex::encode(puzzle_hash.as_ref())
+        ))
+        .unwrap();
+        let spend = run_puzzle(
+            CoinId::new([0x22; 32]),
+            &puzzle,
+            &Program::nil(),
+            MAX_BLOCK_COST_CLVM,
+        )
+        .unwrap();
+
+        assert_eq!(spend.clvm_cost, 20);
+        assert_eq!(spend.condition_cost(), CREATE_COIN_COST);
+        assert_eq!(spend.total_cost(), 20 + CREATE_COIN_COST);
+        assert_eq!(spend.created_coins().collect::<Vec<_>>(), [(
+            puzzle_hash,
+            Mojos(1000)
+        )]);
+        assert_eq!(spend.reserved_fee(), Some(Mojos(10)));
+        assert_eq!(spend.conditions.len(), 3);
+    }
+
+    #[test]
+    fn run_puzzle_sanitizes_timelocks() {
+        let puzzle = Program::assemble(
+            "(q . ((80 -5) (82 -1) (85 0x010000000000000000) (87 0x0100000000)))",
+        )
+        .unwrap();
+        let spend = run_puzzle(
+            CoinId::new([0x22; 32]),
+            &puzzle,
+            &Program::nil(),
+            MAX_BLOCK_COST_CLVM,
+        )
+        .unwrap();
+
+        assert_eq!(spend.conditions, [
+            SpendCondition::AssertSecondsRelative { seconds: 0 },
+            SpendCondition::AssertHeightRelative { height: 0 },
+            SpendCondition::AssertBeforeSecondsAbsolute { seconds: u64::MAX },
+            SpendCondition::AssertBeforeHeightAbsolute { height: u32::MAX },
+        ]);
+    }
+
+    #[test]
+    fn run_puzzle_rejects_invalid_conditions() {
+        let solution = Program::nil();
+        for source in [
+            "(q . 1)",
+            "(q . ((51 0x11 1)))",
+            "(q . ((51 0x{} -1)))",
+            "(q . ((52 -1)))",
+            "(q . ((84 -1)))",
+            "(q . ((83 0x0100000000)))",
+            "(q . ((80 0x0001)))",
+            "(x)",
+        ] {
+            let puzzle = Program::assemble(&source.replace("{}", &"11".repeat(32))).unwrap();
+            let result = run_puzzle(
+                CoinId::new([0; 32]),
+                &puzzle,
+                &solution,
+                MAX_BLOCK_COST_CLVM,
+            );
+            assert!(result.is_err(), "{}", source);
+        }
+    }
+}
@@ -0,0 +1,446 @@
+//! A native CLVM toolkit: the [`Program`] tree, its binary serialization, the `clvm_tools`
+//! text form, `sha256tree` hashing, currying, and a runtime for extracting conditions.
+
+mod conditions;
+mod run;
+mod serialize;
+mod text;
+
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    str::FromStr,
+    sync::{Arc, OnceLock},
+};
+
+use num_bigint::{BigInt, Sign};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+
+pub(crate) use self::run::GROUP_ORDER;
+pub use self::{
+    conditions::{run_puzzle, SpendCondition, SpendConditions, CREATE_COIN_COST},
+    run::MAX_BLOCK_COST_CLVM,
+};
+use crate::{types::Bytes32, util::decode_hex, Error};
+
+const OP_QUOTE: u8 = 1;
+const OP_APPLY: u8 = 2;
+const OP_CONS: u8 = 4;
+
+/// A CLVM value: either an atom of bytes or a pair of values. Pairs share their children, so
+/// cloning a program is cheap.
+///
+/// Programs from the network can nest far deeper than the call stack allows, so dropping,
+/// comparing, hashing, serializing and printing them all walk the tree with a heap stack.
+#[derive(Clone)]
+pub enum Program {
+    Atom(Vec<u8>),
+    Pair(Arc<Program>, Arc<Program>),
+}
+
+impl Default for Program {
+    fn default() -> Self {
+        Program::nil()
+    }
+}
+
+/// Takes uniquely owned pairs apart on the heap instead of letting the compiler generated
+/// drop recurse into them.
+impl Drop for Program {
+    fn drop(&mut self) {
+        let mut pairs = Vec::new();
+        take_pair_children(self, &mut pairs);
+        while let Some(pair) = pairs.pop() {
+            if let Some(mut pair) = Arc::into_inner(pair) {
+                take_pair_children(&mut pair, &mut pairs);
+            }
+        }
+    }
+}
+
+/// Moves the children of `program` that are pairs onto `pairs`, leaving nil in their place.
+fn take_pair_children(program: &mut Program, pairs: &mut Vec<Arc<Program>>) {
+    static NIL: OnceLock<Arc<Program>> = OnceLock::new();
+    if let Program::Pair(first, rest) = program {
+        for child in [first, rest] {
+            if child.is_pair() {
+                let nil = NIL.get_or_init(|| Arc::new(Program::nil())).clone();
+                pairs.push(std::mem::replace(child, nil));
+            }
+        }
+    }
+}
+
+impl PartialEq for Program {
+    fn eq(&self, other: &Program) -> bool {
+        let mut pending = vec![(self, other)];
+        while let Some(pair) = pending.pop() {
+            match pair {
+                (Program::Atom(a), Program::Atom(b)) if a == b => {},
+                (Program::Pair(a_first, a_rest), Program::Pair(b_first, b_rest)) => {
+                    if !Arc::ptr_eq(a_rest, b_rest) {
+                        pending.push((a_rest, b_rest));
+                    }
+                    if !Arc::ptr_eq(a_first, b_first) {
+                        pending.push((a_first, b_first));
+                    }
+                },
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
+impl Eq for Program {}
+
+impl Hash for Program {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let mut pending = vec![self];
+        while let Some(program) = pending.pop() {
+            match program {
+                Program::Atom(atom) => {
+                    state.write_u8(0);
+                    atom.hash(state);
+                },
+                Program::Pair(first, rest) => {
+                    state.write_u8(1);
+                    pending.push(rest);
+                    pending.push(first);
+                },
+            }
+        }
+    }
+}
+
+impl Program {
+    pub fn nil() -> Program {
+        Program::Atom(Vec::new())
+    }
+
+    pub fn atom(bytes: impl Into<Vec<u8>>) -> Program {
+        Program::Atom(bytes.into())
+    }
+
+    pub fn cons(first: Program, rest: Program) -> Program {
+        Program::Pair(Arc::new(first), Arc::new(rest))
+    }
+
+    /// Builds a proper list `(a b c)` from its items.
+    pub fn list(items: impl IntoIterator<Item = Program>) -> Program {
+        let items: Vec<Program> = items.into_iter().collect();
+        items
+            .into_iter()
+            .rev()
+            .fold(Program::nil(), |rest, item| Program::cons(item, rest))
+    }
+
+    pub fn from_int(value: impl Into<BigInt>) -> Program {
+        Program::Atom(bigint_to_atom(&value.into()))
+    }
+
+    pub fn is_nil(&self) -> bool {
+        matches!(self, Program::Atom(atom) if atom.is_empty())
+    }
+
+    pub fn is_pair(&self) -> bool {
+        matches!(self, Program::Pair(..))
+    }
+
+    pub fn as_atom(&self) -> Option<&[u8]> {
+        match self {
+            Program::Atom(atom) => Some(atom),
+            Program::Pair(..) => None,
+        }
+    }
+
+    pub fn as_pair(&self) -> Option<(&Program, &Program)> {
+        match self {
+            Program::Pair(first, rest) => Some((first, rest)),
+            Program::Atom(_) => None,
+        }
+    }
+
+    pub fn first(&self) -> Option<&Program> {
+        self.as_pair().map(|(first, _)| first)
+    }
+
+    pub fn rest(&self) -> Option<&Program> {
+        self.as_pair().map(|(_, rest)| rest)
+    }
+
+    /// Reads an atom as a signed big-endian integer.
+    pub fn as_int(&self) -> Option<BigInt> {
+        self.as_atom().map(atom_to_bigint)
+    }
+
+    /// Reads an atom as an unsigned integer, failing on negative or oversized values.
+    pub fn as_u64(&self) -> Option<u64> {
+        u64::try_from(self.as_int()?).ok()
+    }
+
+    /// Iterates over the items of a list, stopping at the first non-pair tail.
+    pub fn iter(&self) -> ListIter<'_> {
+        ListIter { current: self }
+    }
+
+    /// Returns the items of a proper (nil terminated) list.
+    pub fn to_list(&self) -> Option<Vec<&Program>> {
+        let items: Vec<&Program> = self.iter().collect();
+        let mut tail = self;
+        while let Some(rest) = tail.rest() {
+            tail = rest;
+        }
+        tail.is_nil().then_some(items)
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Program, Error> {
+        Program::from_bytes(&decode_hex(hex)?)
+    }
+
+    pub fn to_hex(&self) -> String {
+        format!("0x{}", hex::encode(self.to_bytes()))
+    }
+
+    /// The `sha256tree` of the program, which for a puzzle is its puzzle hash.
+    pub fn tree_hash(&self) -> Bytes32 {
+        enum Op<'a> {
+            Visit(&'a Program),
+            Cons,
+        }
+
+        let mut ops = vec![Op::Visit(self)];
+        let mut hashes: Vec<[u8; 32]> = Vec::new();
+        while let Some(op) = ops.pop() {
+            match op {
+                Op::Visit(Program::Atom(atom)) => hashes.push(hash_atom(atom)),
+                Op::Visit(Program::Pair(first, rest)) => {
+                    ops.push(Op::Cons);
+                    ops.push(Op::Visit(rest));
+                    ops.push(Op::Visit(first));
+                },
+                Op::Cons => {
+                    let rest = hashes.pop().expect("rest hash");
+                    let first = hashes.pop().expect("first hash");
+                    hashes.push(hash_pair(&first, &rest));
+                },
+            }
+        }
+        Bytes32::new(hashes.pop().expect("tree hash"))
+    }
+
+    /// Curries `args` into the program: `(a (q . program) (c (q . arg1) (c (q . arg2) 1)))`.
+    pub fn curry(&self, args: &[Program]) -> Program {
+        let args = args.iter().rev().fold(Program::from_int(1), |rest, arg| {
+            Program::list([
+                Program::atom([OP_CONS]),
+                Program::cons(Program::atom([OP_QUOTE]), arg.clone()),
+                rest,
+            ])
+        });
+        Program::list([
+            Program::atom([OP_APPLY]),
+            Program::cons(Program::atom([OP_QUOTE]), self.clone()),
+            args,
+        ])
+    }
+
+    /// Splits a curried program into its module and curried arguments, or returns `None` if
+    /// the program does not have the shape produced by [`Program::curry`].
+    pub fn uncurry(&self) -> Option<(Program, Vec<Program>)> {
+        let items = self.to_list()?;
+        let [op, quoted_mod, mut args] = items[..] else {
+            return None;
+        };
+        if op.as_atom()? != [OP_APPLY] {
+            return None;
+        }
+        let (q, module) = quoted_mod.as_pair()?;
+        if q.as_atom()? != [OP_QUOTE] {
+            return None;
+        }
+        let mut curried = Vec::new();
+        while args.is_pair() {
+            let items = args.to_list()?;
+            let [op, quoted_arg, rest] = items[..] else {
+                return None;
+            };
+            if op.as_atom()? != [OP_CONS] {
+                return None;
+            }
+            let (q, arg) = quoted_arg.as_pair()?;
+            if q.as_atom()? != [OP_QUOTE] {
+                return None;
+            }
+            curried.push(arg.clone());
+            args = rest;
+        }
+        (args.as_atom()? == [1]).then(|| (module.clone(), curried))
+    }
+}
+
+/// Computes the tree hash of `mod_hash` curried with arguments whose tree hashes are
+/// `arg_hashes`, without needing the arguments themselves.
+pub fn curry_tree_hash(mod_hash: &Bytes32, arg_hashes: &[Bytes32]) -> Bytes32 {
+    let quote = hash_atom(&[OP_QUOTE]);
+    let apply = hash_atom(&[OP_APPLY]);
+    let cons = hash_atom(&[OP_CONS]);
+    let nil = hash_atom(&[]);
+    let quoted = |hash: &[u8; 32]| hash_pair(&quote, hash);
+    let list = |items: &[[u8; 32]]| {
+        items
+            .iter()
+            .rev()
+            .fold(nil, |rest, item| hash_pair(item, &rest))
+    };
+
+    let args = arg_hashes.iter().rev().fold(hash_atom(&[1]), |rest, arg| {
+        list(&[cons, quoted(arg.as_bytes()), rest])
+    });
+    Bytes32::new(list(&[apply, quoted(mod_hash.as_bytes()), args]))
+}
+
+fn hash_atom(atom: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([1]);
+    hasher.update(atom);
+    hasher.finalize().into()
+}
+
+fn hash_pair(first: &[u8; 32], rest: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([2]);
+    hasher.update(first);
+    hasher.update(rest);
+    hasher.finalize().into()
+}
+
+/// Encodes an integer as a CLVM atom: minimal length two's complement, with zero as the empty
+/// atom.
+pub(crate) fn bigint_to_atom(value: &BigInt) -> Vec<u8> {
+    if value.sign() == Sign::NoSign {
+        return Vec::new();
+    }
+    value.to_signed_bytes_be()
+}
+
+pub(crate) fn atom_to_bigint(atom: &[u8]) -> BigInt {
+    BigInt::from_signed_bytes_be(atom)
+}
+
+pub struct ListIter<'a> {
+    current: &'a Program,
+}
+
+impl<'a> Iterator for ListIter<'a> {
+    type Item = &'a Program;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (first, rest) = self.current.as_pair()?;
+        self.current = rest;
+        Some(first)
+    }
+}
+
+impl From<Vec<u8>> for Program {
+    fn from(bytes: Vec<u8>) -> Self {
+        Program::Atom(bytes)
+    }
+}
+
+impl From<Bytes32> for Program {
+    fn from(bytes: Bytes32) -> Self {
+        Program::atom(bytes.as_bytes().to_vec())
+    }
+}
+
+/// Parses the `clvm_tools` text form, e.g. `(q . "hello")`.
+impl FromStr for Program {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Program::assemble(s)
+    }
+}
+
+impl fmt::Display for Program {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.disassemble())
+    }
+}
+
+impl fmt::Debug for Program {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Program({})", self.disassemble())
+    }
+}
+
+/// Programs travel as serialized hex in RPC payloads.
+impl Serialize for Program {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_hex())
+    }
+}
+
+impl<'de> Deserialize<'de> for Program {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Program::from_hex(&s).map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deeply_nested_program_does_not_overflow_the_stack() {
+        const DEPTH: usize = 200_000;
+        let mut bytes = vec![0xff; DEPTH];
+        bytes.resize(2 * DEPTH + 1, 0x80);
+
+        let program = Program::from_bytes(&bytes).unwrap();
+        assert_eq!(program.to_bytes(), bytes);
+
+        let text = program.disassemble();
+        assert_eq!(
+            text,
+            format!("{}(){}", "(".repeat(DEPTH), ")".repeat(DEPTH))
+        );
+        let assembled = Program::assemble(&text).unwrap();
+        assert_eq!(assembled, program);
+
+        let mut hashes = std::collections::HashSet::new();
+        hashes.insert(assembled);
+        assert!(hashes.contains(&program));
+        drop(hashes);
+        drop(program);
+    }
+
+    #[test]
+    fn assemble_round_trips_dotted_and_nested_lists() {
+        for source in [
+            "(a . b)",
+            "(100 (200 300) . 400)",
+            "((() . 1) ())",
+            "(q . (1 2))",
+        ] {
+            let program = Program::assemble(source).unwrap();
+            assert_eq!(Program::assemble(&program.disassemble()).unwrap(), program);
+        }
+        assert_eq!(
+            Program::assemble("(100 (200 300) . 400)")
+                .unwrap()
+                .disassemble(),
+            "(100 (200 300) . 400)"
+        );
+        assert_eq!(
+            Program::assemble("(q . (1 2))").unwrap().disassemble(),
+            "(q 1 2)"
+        );
+        for source in ["(. 1)", "(1 . )", "(1 . 2 3)", "(1 2", ")"] {
+            assert!(Program::assemble(source).is_err(), "{}", source);
+        }
+    }
+}
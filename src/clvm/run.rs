@@ -0,0 +1,824 @@
+use bls12_381::{G1Affine, G1Projective, Scalar};
+use num_bigint::{BigInt, BigUint, Sign};
+use num_integer::Integer;
+use sha2::{Digest, Sha256};
+
+use super::{atom_to_bigint, bigint_to_atom, serialize::path_bits, Program};
+use crate::{bls::hash_to_g1, Error};
+
+/// The most CLVM cost a block may spend on running programs; a sensible limit for any spend.
+pub const MAX_BLOCK_COST_CLVM: u64 = 11_000_000_000;
+
+const QUOTE_COST: u64 = 20;
+const APPLY_COST: u64 = 90;
+const TRAVERSE_BASE_COST: u64 = 40;
+const TRAVERSE_COST_PER_ZERO_BYTE: u64 = 4;
+const TRAVERSE_COST_PER_BIT: u64 = 4;
+const MALLOC_COST_PER_BYTE: u64 = 10;
+
+const IF_COST: u64 = 33;
+const CONS_COST: u64 = 50;
+const FIRST_COST: u64 = 30;
+const REST_COST: u64 = 30;
+const LISTP_COST: u64 = 19;
+const EQ_BASE_COST: u64 = 117;
+const EQ_COST_PER_BYTE: u64 = 1;
+const GRS_BASE_COST: u64 = 117;
+const GRS_COST_PER_BYTE: u64 = 1;
+const SHA256_BASE_COST: u64 = 87;
+const SHA256_COST_PER_ARG: u64 = 134;
+const SHA256_COST_PER_BYTE: u64 = 2;
+const SUBSTR_BASE_COST: u64 = 1;
+const STRLEN_BASE_COST: u64 = 173;
+const STRLEN_COST_PER_BYTE: u64 = 1;
+const CONCAT_BASE_COST: u64 = 142;
+const CONCAT_COST_PER_ARG: u64 = 135;
+const CONCAT_COST_PER_BYTE: u64 = 3;
+const ARITH_BASE_COST: u64 = 99;
+const ARITH_COST_PER_ARG: u64 = 320;
+const ARITH_COST_PER_BYTE: u64 = 3;
+const MUL_BASE_COST: u64 = 92;
+const MUL_COST_PER_OP: u64 = 885;
+const MUL_LINEAR_COST_PER_BYTE: u64 = 6;
+const MUL_SQUARE_COST_PER_BYTE_DIVIDER: u64 = 128;
+const DIV_BASE_COST: u64 = 988;
+const DIV_COST_PER_BYTE: u64 = 4;
+const DIVMOD_BASE_COST: u64 = 1116;
+const DIVMOD_COST_PER_BYTE: u64 = 6;
+const GR_BASE_COST: u64 = 498;
+const GR_COST_PER_BYTE: u64 = 2;
+const ASHIFT_BASE_COST: u64 = 596;
+const ASHIFT_COST_PER_BYTE: u64 = 3;
+const LSHIFT_BASE_COST: u64 = 277;
+const LSHIFT_COST_PER_BYTE: u64 = 3;
+const LOG_BASE_COST: u64 = 100;
+const LOG_COST_PER_ARG: u64 = 264;
+const LOG_COST_PER_BYTE: u64 = 3;
+const LOGNOT_BASE_COST: u64 = 331;
+const LOGNOT_COST_PER_BYTE: u64 = 3;
+const BOOL_BASE_COST: u64 = 200;
+const BOOL_COST_PER_ARG: u64 = 300;
+const POINT_ADD_BASE_COST: u64 = 101_094;
+const POINT_ADD_COST_PER_ARG: u64 = 1_343_980;
+const PUBKEY_BASE_COST: u64 = 1_325_730;
+const PUBKEY_COST_PER_BYTE: u64 = 38;
+const G1_SUBTRACT_BASE_COST: u64 = 101_094;
+const G1_SUBTRACT_COST_PER_ARG: u64 = 1_343_980;
+const G1_MULTIPLY_BASE_COST: u64 = 705_500;
+const G1_MULTIPLY_COST_PER_BYTE: u64 = 10;
+const G1_NEGATE_BASE_COST: u64 = 1_396;
+const G1_MAP_BASE_COST: u64 = 195_000;
+const G1_MAP_COST_PER_BYTE: u64 = 4;
+const G1_MAP_COST_PER_DST_BYTE: u64 = 4;
+const COINID_COST: u64 = 800;
+const MODPOW_BASE_COST: u64 = 17_000;
+const MODPOW_COST_PER_BYTE_BASE_VALUE: u64 = 38;
+const MODPOW_COST_PER_BYTE_EXPONENT: u64 = 3;
+const MODPOW_COST_PER_BYTE_MOD: u64 = 21;
+
+/// What `g1_map` hashes with when no domain separation tag is given.
+const G1_MAP_DEFAULT_DST: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_AUG_";
+
+/// The order of the BLS12-381 G1 group, which `pubkey_for_exp` reduces exponents by.
+pub(crate) const GROUP_ORDER: [u8; 32] = [
+    0x73, 0xed, 0xa7, 0x53, 0x29, 0x9d, 0x7d, 0x48, 0x33, 0x39, 0xd8, 0x08, 0x09, 0xa1, 0xd8, 0x05,
+    0x53, 0xbd, 0xa4, 0x02, 0xff, 0xfe, 0x5b, 0xfe, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01,
+];
+
+enum Op {
+    Eval(Program, Program),
+    Apply(Vec<u8>, usize),
+}
+
+impl Program {
+    /// Runs the program against `env`, returning the CLVM cost spent and the result. Fails
+    /// once the cost exceeds `max_cost`, checked before each expensive operation rather than
+    /// after it, on `x`, and on operators this runtime does not implement: `g2_add`,
+    /// `g2_subtract`, `g2_multiply`, `g2_negate`, `g2_map`, `bls_pairing_identity`,
+    /// `bls_verify`, `secp256k1_verify` and `secp256r1_verify`.
+    pub fn run(&self, env: &Program, max_cost: u64) -> Result<(u64, Program), Error> {
+        let mut cost = 0;
+        let mut ops = vec![Op::Eval(self.clone(), env.clone())];
+        let mut values: Vec<Program> = Vec::new();
+        while let Some(op) = ops.pop() {
+            match op {
+                Op::Eval(Program::Atom(ref path), env) => {
+                    let (path_cost, value) = traverse(&env, path)?;
+                    cost += path_cost;
+                    values.push(value);
+                },
+                Op::Eval(Program::Pair(ref operator, ref operands), env) => {
+                    let operator = match operator.as_ref() {
+                        Program::Atom(operator) => operator.clone(),
+                        Program::Pair(..) => {
+                            return Err(clvm_error("operator must be an atom", operator))
+                        },
+                    };
+                    if operator == [1] {
+                        cost += QUOTE_COST;
+                        values.push(operands.as_ref().clone());
+                    } else {
+                        let operands = operands
+                            .to_list()
+                            .ok_or_else(|| clvm_error("bad operand list", operands))?;
+                        ops.push(Op::Apply(operator, operands.len()));
+                        for operand in operands.into_iter().rev() {
+                            ops.push(Op::Eval(operand.clone(), env.clone()));
+                        }
+                    }
+                },
+                Op::Apply(operator, count) => {
+                    let args = values.split_off(values.len() - count);
+                    if operator == [2] {
+                        let [program, env] = <[Program; 2]>::try_from(args).map_err(|_| {
+                            Error::ClvmError("apply takes exactly 2 parameters".to_string())
+                        })?;
+                        cost += APPLY_COST;
+                        ops.push(Op::Eval(program, env));
+                    } else {
+                        let (op_cost, value) = run_operator(&operator, &args, max_cost - cost)?;
+                        cost += op_cost;
+                        values.push(value);
+                    }
+                },
+            }
+            check_cost(cost, max_cost)?;
+        }
+        Ok((cost, values.pop().expect("program result")))
+    }
+}
+
+fn clvm_error(message: &str, program: &Program) -> Error {
+    Error::ClvmError(format!("{}: {}", message, program))
+}
+
+fn traverse(env: &Program, path: &[u8]) -> Result<(u64, Program), Error> {
+    let zero_bytes = path.iter().take_while(|b| **b == 0).count() as u64;
+    let mut cost = TRAVERSE_BASE_COST + zero_bytes * TRAVERSE_COST_PER_ZERO_BYTE;
+    if zero_bytes as usize == path.len() {
+        return Ok((cost, Program::nil()));
+    }
+    let mut current = env;
+    for rest in path_bits(path) {
+        current = match (current, rest) {
+            (Program::Pair(_, r), true) => r,
+            (Program::Pair(f, _), false) => f,
+            (Program::Atom(_), _) => {
+                return Err(clvm_error("path into atom", &Program::atom(path)));
+            },
+        };
+        cost += TRAVERSE_COST_PER_BIT;
+    }
+    Ok((cost, current.clone()))
+}
+
+/// Fails once `cost` is over `max_cost`. Operators call this with the cost they are about to
+/// incur, before doing the work, so oversized operands cannot pin the CPU.
+fn check_cost(cost: u64, max_cost: u64) -> Result<(), Error> {
+    if cost > max_cost {
+        return Err(Error::ClvmError("cost exceeded".to_string()));
+    }
+    Ok(())
+}
+
+fn malloc_cost(cost: u64, value: &Program) -> u64 {
+    cost + value.as_atom().map_or(0, |a| a.len() as u64) * MALLOC_COST_PER_BYTE
+}
+
+fn bool_atom(value: bool) -> Program {
+    match value {
+        true => Program::atom([1]),
+        false => Program::nil(),
+    }
+}
+
+fn check_arg_count(args: &[Program], count: usize, name: &str) -> Result<(), Error> {
+    if args.len() != count {
+        let plural = if count == 1 { "" } else { "s" };
+        return Err(Error::ClvmError(format!(
+            "{} takes exactly {} argument{}",
+            name, count, plural
+        )));
+    }
+    Ok(())
+}
+
+fn atom<'a>(arg: &'a Program, name: &str) -> Result<&'a [u8], Error> {
+    arg.as_atom()
+        .ok_or_else(|| clvm_error(&format!("{} on list", name), arg))
+}
+
+fn int(arg: &Program, name: &str) -> Result<(BigInt, u64), Error> {
+    let atom = atom(arg, name)?;
+    Ok((atom_to_bigint(atom), atom.len() as u64))
+}
+
+fn shift_amount(arg: &Program, name: &str) -> Result<i64, Error> {
+    let atom = atom(arg, name)?;
+    if atom.len() > 4 {
+        return Err(clvm_error(&format!("{} requires int32 args", name), arg));
+    }
+    let shift = i64::try_from(atom_to_bigint(atom)).expect("int32");
+    if !(-65535..=65535).contains(&shift) {
+        return Err(clvm_error("shift too large", arg));
+    }
+    Ok(shift)
+}
+
+fn int_program(value: &BigInt) -> Program {
+    Program::Atom(bigint_to_atom(value))
+}
+
+fn g1_point(arg: &Program, name: &str) -> Result<G1Projective, Error> {
+    let bytes: [u8; 48] = atom(arg, name)?
+        .try_into()
+        .map_err(|_| clvm_error(&format!("{} requires G1 points", name), arg))?;
+    Option::<G1Affine>::from(G1Affine::from_compressed(&bytes))
+        .map(|point| G1Projective::from(&point))
+        .ok_or_else(|| clvm_error("point is not a valid G1 element", arg))
+}
+
+fn g1_atom(point: G1Projective) -> Program {
+    Program::atom(G1Affine::from(point).to_compressed().to_vec())
+}
+
+/// Reduces an integer by the group order, as `pubkey_for_exp` and `g1_multiply` do.
+fn group_scalar(value: &BigInt) -> Scalar {
+    let order = BigInt::from_bytes_be(Sign::Plus, &GROUP_ORDER);
+    let (_, mut bytes) = value.mod_floor(&order).to_bytes_le();
+    bytes.resize(32, 0);
+    Scalar::from_bytes(&bytes.try_into().expect("32 bytes")).expect("reduced scalar")
+}
+
+/// Runs one operator with what is left of the budget as `max_cost`.
+fn run_operator(operator: &[u8], args: &[Program], max_cost: u64) -> Result<(u64, Program), Error> {
+    let op = match operator {
+        [op] => *op,
+        _ => {
+            return Err(clvm_error(
+                "unimplemented operator",
+                &Program::atom(operator),
+            ))
+        },
+    };
+    match op {
+        // i
+        0x03 => {
+            check_arg_count(args, 3, "i")?;
+            let value = if args[0].is_nil() { &args[2] } else { &args[1] };
+            Ok((IF_COST, value.clone()))
+        },
+        // c
+        0x04 => {
+            check_arg_count(args, 2, "c")?;
+            Ok((CONS_COST, Program::cons(args[0].clone(), args[1].clone())))
+        },
+        // f
+        0x05 => {
+            check_arg_count(args, 1, "f")?;
+            let first = args[0]
+                .first()
+                .ok_or_else(|| clvm_error("first of non-cons", &args[0]))?;
+            Ok((FIRST_COST, first.clone()))
+        },
+        // r
+        0x06 => {
+            check_arg_count(args, 1, "r")?;
+            let rest = args[0]
+                .rest()
+                .ok_or_else(|| clvm_error("rest of non-cons", &args[0]))?;
+            Ok((REST_COST, rest.clone()))
+        },
+        // l
+        0x07 => {
+            check_arg_count(args, 1, "l")?;
+            Ok((LISTP_COST, bool_atom(args[0].is_pair())))
+        },
+        // x
+        0x08 => Err(clvm_error("clvm raise", &Program::list(args.to_vec()))),
+        // =
+        0x09 => {
+            check_arg_count(args, 2, "=")?;
+            let (a0, a1) = (atom(&args[0], "=")?, atom(&args[1], "=")?);
+            let cost = EQ_BASE_COST + (a0.len() + a1.len()) as u64 * EQ_COST_PER_BYTE;
+            Ok((cost, bool_atom(a0 == a1)))
+        },
+        // >s
+        0x0a => {
+            check_arg_count(args, 2, ">s")?;
+            let (a0, a1) = (atom(&args[0], ">s")?, atom(&args[1], ">s")?);
+            let cost = GRS_BASE_COST + (a0.len() + a1.len()) as u64 * GRS_COST_PER_BYTE;
+            Ok((cost, bool_atom(a0 > a1)))
+        },
+        // sha256
+        0x0b => {
+            let mut cost = SHA256_BASE_COST;
+            let mut hasher = Sha256::new();
+            for arg in args {
+                let bytes = atom(arg, "sha256")?;
+                cost += SHA256_COST_PER_ARG + bytes.len() as u64 * SHA256_COST_PER_BYTE;
+                hasher.update(bytes);
+            }
+            let value = Program::atom(hasher.finalize().to_vec());
+            Ok((malloc_cost(cost, &value), value))
+        },
+        // substr
+        0x0c => {
+            if !(2..=3).contains(&args.len()) {
+                return Err(Error::ClvmError(
+                    "substr takes exactly 2 or 3 arguments".to_string(),
+                ));
+            }
+            let bytes = atom(&args[0], "substr")?;
+            let index = |arg: &Program| -> Result<usize, Error> {
+                let (value, _) = int(arg, "substr")?;
+                usize::try_from(value).map_err(|_| clvm_error("invalid indices for substr", arg))
+            };
+            let start = index(&args[1])?;
+            let end = match args.get(2) {
+                Some(arg) => index(arg)?,
+                None => bytes.len(),
+            };
+            if end > bytes.len() || start > end {
+                return Err(clvm_error("invalid indices for substr", &args[0]));
+            }
+            Ok((SUBSTR_BASE_COST, Program::atom(&bytes[start..end])))
+        },
+        // strlen
+        0x0d => {
+            check_arg_count(args, 1, "strlen")?;
+            let len = atom(&args[0], "strlen")?.len() as u64;
+            let value = Program::from_int(len);
+            let cost = STRLEN_BASE_COST + len * STRLEN_COST_PER_BYTE;
+            Ok((malloc_cost(cost, &value), value))
+        },
+        // concat
+        0x0e => {
+            let atoms = args
+                .iter()
+                .map(|arg| atom(arg, "concat"))
+                .collect::<Result<Vec<_>, Error>>()?;
+            let len = atoms.iter().map(|atom| atom.len() as u64).sum::<u64>();
+            let cost = CONCAT_BASE_COST
+                + atoms.len() as u64 * CONCAT_COST_PER_ARG
+                + len * CONCAT_COST_PER_BYTE;
+            check_cost(cost, max_cost)?;
+            let value = Program::Atom(atoms.concat());
+            Ok((malloc_cost(cost, &value), value))
+        },
+        // + and -
+        0x10 | 0x11 => {
+            let name = if op == 0x10 { "+" } else { "-" };
+            let mut cost = ARITH_BASE_COST;
+            let mut byte_count = 0;
+            let mut total = BigInt::from(0);
+            for (i, arg) in args.iter().enumerate() {
+                let (value, len) = int(arg, name)?;
+                cost += ARITH_COST_PER_ARG;
+                byte_count += len;
+                if op == 0x11 && i > 0 {
+                    total -= value;
+                } else {
+                    total += value;
+                }
+            }
+            let value = int_program(&total);
+            Ok((
+                malloc_cost(cost + byte_count * ARITH_COST_PER_BYTE, &value),
+                value,
+            ))
+        },
+        // *
+        0x12 => {
+            let mut cost = MUL_BASE_COST;
+            let mut args = args.iter();
+            let Some(first) = args.next() else {
+                return Ok((cost, Program::atom([1])));
+            };
+            let (mut total, mut l0) = int(first, "*")?;
+            for arg in args {
+                let (value, l1) = int(arg, "*")?;
+                cost += MUL_COST_PER_OP;
+                cost += (l0 + l1) * MUL_LINEAR_COST_PER_BYTE;
+                cost += l0.saturating_mul(l1) / MUL_SQUARE_COST_PER_BYTE_DIVIDER;
+                check_cost(cost, max_cost)?;
+                total *= value;
+                l0 = bigint_to_atom(&total).len() as u64;
+            }
+            let value = int_program(&total);
+            Ok((malloc_cost(cost, &value), value))
+        },
+        // /
+        0x13 => {
+            check_arg_count(args, 2, "/")?;
+            let ((a0, l0), (a1, l1)) = (int(&args[0], "/")?, int(&args[1], "/")?);
+            if a1.sign() == Sign::NoSign {
+                return Err(clvm_error("div with 0", &args[0]));
+            }
+            let cost = DIV_BASE_COST + (l0 + l1) * DIV_COST_PER_BYTE;
+            check_cost(cost, max_cost)?;
+            let value = int_program(&a0.div_floor(&a1));
+            Ok((malloc_cost(cost, &value), value))
+        },
+        // divmod
+        0x14 => {
+            check_arg_count(args, 2, "divmod")?;
+            let ((a0, l0), (a1, l1)) = (int(&args[0], "divmod")?, int(&args[1], "divmod")?);
+            if a1.sign() == Sign::NoSign {
+                return Err(clvm_error("divmod with 0", &args[0]));
+            }
+            let cost = DIVMOD_BASE_COST + (l0 + l1) * DIVMOD_COST_PER_BYTE;
+            check_cost(cost, max_cost)?;
+            let (q, r) = a0.div_mod_floor(&a1);
+            let (q, r) = (int_program(&q), int_program(&r));
+            let cost = malloc_cost(malloc_cost(cost, &q), &r);
+            Ok((cost, Program::cons(q, r)))
+        },
+        // >
+        0x15 => {
+            check_arg_count(args, 2, ">")?;
+            let ((a0, l0), (a1, l1)) = (int(&args[0], ">")?, int(&args[1], ">")?);
+            Ok((
+                GR_BASE_COST + (l0 + l1) * GR_COST_PER_BYTE,
+                bool_atom(a0 > a1),
+            ))
+        },
+        // ash
+        0x16 => {
+            check_arg_count(args, 2, "ash")?;
+            let (a0, l0) = int(&args[0], "ash")?;
+            let shift = shift_amount(&args[1], "ash")?;
+            let result = if shift >= 0 {
+                a0 << shift as usize
+            } else {
+                a0 >> (-shift) as usize
+            };
+            let value = int_program(&result);
+            let len = value.as_atom().map_or(0, |a| a.len()) as u64;
+            let cost = ASHIFT_BASE_COST + (l0 + len) * ASHIFT_COST_PER_BYTE;
+            Ok((malloc_cost(cost, &value), value))
+        },
+        // lsh
+        0x17 => {
+            check_arg_count(args, 2, "lsh")?;
+            let a0 = atom(&args[0], "lsh")?;
+            let shift = shift_amount(&args[1], "lsh")?;
+            let unsigned = BigUint::from_bytes_be(a0);
+            let result = if shift >= 0 {
+                unsigned << shift as usize
+            } else {
+                unsigned >> (-shift) as usize
+            };
+            let value = int_program(&BigInt::from(result));
+            let len = value.as_atom().map_or(0, |a| a.len()) as u64;
+            let cost = LSHIFT_BASE_COST + (a0.len() as u64 + len) * LSHIFT_COST_PER_BYTE;
+            Ok((malloc_cost(cost, &value), value))
+        },
+        // logand, logior and logxor
+        0x18..=0x1a => {
+            let name = ["logand", "logior", "logxor"][(op - 0x18) as usize];
+            let mut cost = LOG_BASE_COST;
+            let mut total = BigInt::from(if op == 0x18 { -1 } else { 0 });
+            for arg in args {
+                let (value, len) = int(arg, name)?;
+                cost += LOG_COST_PER_ARG + len * LOG_COST_PER_BYTE;
+                total = match op {
+                    0x18 => total & value,
+                    0x19 => total | value,
+                    _ => total ^ value,
+                };
+            }
+            let value = int_program(&total);
+            Ok((malloc_cost(cost, &value), value))
+        },
+        // lognot
+        0x1b => {
+            check_arg_count(args, 1, "lognot")?;
+            let (a0, l0) = int(&args[0], "lognot")?;
+            let value = int_program(&(-a0 - 1));
+            Ok((
+                malloc_cost(LOGNOT_BASE_COST + l0 * LOGNOT_COST_PER_BYTE, &value),
+                value,
+            ))
+        },
+        // point_add
+        0x1d => {
+            let mut cost = POINT_ADD_BASE_COST;
+            let mut total = G1Projective::identity();
+            for arg in args {
+                cost += POINT_ADD_COST_PER_ARG;
+                check_cost(cost, max_cost)?;
+                total += g1_point(arg, "point_add")?;
+            }
+            let value = g1_atom(total);
+            Ok((malloc_cost(cost, &value), value))
+        },
+        // pubkey_for_exp
+        0x1e => {
+            check_arg_count(args, 1, "pubkey_for_exp")?;
+            let (exponent, len) = int(&args[0], "pubkey_for_exp")?;
+            let cost = PUBKEY_BASE_COST + len * PUBKEY_COST_PER_BYTE;
+            check_cost(cost, max_cost)?;
+            let value = g1_atom(G1Projective::generator() * group_scalar(&exponent));
+            Ok((malloc_cost(cost, &value), value))
+        },
+        // not
+        0x20 => {
+            check_arg_count(args, 1, "not")?;
+            Ok((BOOL_BASE_COST, bool_atom(args[0].is_nil())))
+        },
+        // any and all
+        0x21 | 0x22 => {
+            let cost = BOOL_BASE_COST + args.len() as u64 * BOOL_COST_PER_ARG;
+            let value = match op {
+                0x21 => args.iter().any(|arg| !arg.is_nil()),
+                _ => args.iter().all(|arg| !arg.is_nil()),
+            };
+            Ok((cost, bool_atom(value)))
+        },
+        // softfork
+        0x24 => {
+            let cost = args
+                .first()
+                .and_then(Program::as_u64)
+                .filter(|cost| *cost > 0)
+                .ok_or_else(|| Error::ClvmError("softfork requires a positive cost".to_string()))?;
+            Ok((cost, Program::nil()))
+        },
+        // coinid
+        0x30 => {
+            check_arg_count(args, 3, "coinid")?;
+            let parent = atom(&args[0], "coinid")?;
+            let puzzle_hash = atom(&args[1], "coinid")?;
+            let amount = atom(&args[2], "coinid")?;
+            if parent.len() != 32 || puzzle_hash.len() != 32 {
+                return Err(clvm_error(
+                    "coinid: invalid hash",
+                    &Program::list(args.to_vec()),
+                ));
+            }
+            if amount_is_invalid(amount) {
+                return Err(clvm_error("coinid: invalid amount", &args[2]));
+            }
+            let mut hasher = Sha256::new();
+            hasher.update(parent);
+            hasher.update(puzzle_hash);
+            hasher.update(amount);
+            let value = Program::atom(hasher.finalize().to_vec());
+            Ok((malloc_cost(COINID_COST, &value), value))
+        },
+        // g1_subtract
+        0x31 => {
+            let mut cost = G1_SUBTRACT_BASE_COST;
+            let mut total = G1Projective::identity();
+            for (i, arg) in args.iter().enumerate() {
+                cost += G1_SUBTRACT_COST_PER_ARG;
+                check_cost(cost, max_cost)?;
+                let point = g1_point(arg, "g1_subtract")?;
+                total = if i == 0 { point } else { total - point };
+            }
+            let value = g1_atom(total);
+            Ok((malloc_cost(cost, &value), value))
+        },
+        // g1_multiply
+        0x32 => {
+            check_arg_count(args, 2, "g1_multiply")?;
+            let point = g1_point(&args[0], "g1_multiply")?;
+            let (scalar, len) = int(&args[1], "g1_multiply")?;
+            let cost = G1_MULTIPLY_BASE_COST + len * G1_MULTIPLY_COST_PER_BYTE;
+            check_cost(cost, max_cost)?;
+            let value = g1_atom(point * group_scalar(&scalar));
+            Ok((malloc_cost(cost, &value), value))
+        },
+        // g1_negate
+        0x33 => {
+            check_arg_count(args, 1, "g1_negate")?;
+            let value = g1_atom(-g1_point(&args[0], "g1_negate")?);
+            Ok((malloc_cost(G1_NEGATE_BASE_COST, &value), value))
+        },
+        // g1_map
+        0x38 => {
+            if !(1..=2).contains(&args.len()) {
+                return Err(Error::ClvmError(
+                    "g1_map takes exactly 1 or 2 arguments".to_string(),
+                ));
+            }
+            let message = atom(&args[0], "g1_map")?;
+            let dst = match args.get(1) {
+                Some(arg) => atom(arg, "g1_map")?,
+                None => G1_MAP_DEFAULT_DST,
+            };
+            let cost = G1_MAP_BASE_COST
+                + message.len() as u64 * G1_MAP_COST_PER_BYTE
+                + dst.len() as u64 * G1_MAP_COST_PER_DST_BYTE;
+            check_cost(cost, max_cost)?;
+            let value = g1_atom(hash_to_g1(message, dst));
+            Ok((malloc_cost(cost, &value), value))
+        },
+        // modpow
+        0x3c => {
+            check_arg_count(args, 3, "modpow")?;
+            let (base, lb) = int(&args[0], "modpow")?;
+            let (exponent, le) = int(&args[1], "modpow")?;
+            let (modulus, lm) = int(&args[2], "modpow")?;
+            if exponent.sign() == Sign::Minus {
+                return Err(clvm_error("modpow with negative exponent", &args[1]));
+            }
+            if modulus.sign() == Sign::NoSign {
+                return Err(clvm_error("modpow with 0 modulus", &args[2]));
+            }
+            let cost = MODPOW_BASE_COST
+                + lb * MODPOW_COST_PER_BYTE_BASE_VALUE
+                + le.saturating_mul(le)
+                    .saturating_mul(MODPOW_COST_PER_BYTE_EXPONENT)
+                + lm.saturating_mul(lm)
+                    .saturating_mul(MODPOW_COST_PER_BYTE_MOD);
+            check_cost(cost, max_cost)?;
+            let result = base.modpow(&exponent, &modulus);
+            // Match Python's sign convention: the result takes the sign of the modulus.
+            let result = result.mod_floor(&modulus);
+            let value = int_program(&result);
+            Ok((malloc_cost(cost, &value), value))
+        },
+        // %
+        0x3d => {
+            check_arg_count(args, 2, "%")?;
+            let ((a0, l0), (a1, l1)) = (int(&args[0], "%")?, int(&args[1], "%")?);
+            if a1.sign() == Sign::NoSign {
+                return Err(clvm_error("mod with 0", &args[0]));
+            }
+            let cost = DIV_BASE_COST + (l0 + l1) * DIV_COST_PER_BYTE;
+            check_cost(cost, max_cost)?;
+            let value = int_program(&a0.mod_floor(&a1));
+            Ok((malloc_cost(cost, &value), value))
+        },
+        _ => Err(clvm_error(
+            "unimplemented operator",
+            &Program::atom(operator),
+        )),
+    }
+}
+
+/// Amounts must be canonical, non-negative and fit in a `u64`.
+pub(crate) fn amount_is_invalid(amount: &[u8]) -> bool {
+    let canonical = bigint_to_atom(&atom_to_bigint(amount)) == amount;
+    let unsigned = amount.first().is_none_or(|b| b & 0x80 == 0);
+    let len = match amount.first() {
+        Some(0) => amount.len() - 1,
+        _ => amount.len(),
+    };
+    !canonical || !unsigned || len > 8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(source: &str, env: &str, max_cost: u64) -> Result<(u64, Program), Error> {
+        let program = Program::assemble(source).unwrap();
+        program.run(&Program::assemble(env).unwrap(), max_cost)
+    }
+
+    #[test]
+    fn run_returns_known_outputs_and_costs() {
+        let cases = [
+            // QUOTE_COST.
+            ("(q . 42)", "()", "42", 20),
+            // Path 1 (TRAVERSE_BASE_COST) then FIRST_COST.
+            ("(f 1)", "(5 6)", "5", 70),
+            // Two quotes, ARITH_BASE_COST + 2 * ARITH_COST_PER_ARG + 2 bytes * 3, and one
+            // byte of result at MALLOC_COST_PER_BYTE.
+            ("(+ (q . 2) (q . 3))", "()", "5", 795),
+            // Two quotes and APPLY_COST, then `(f 1)` against the new environment.
+            ("(a (q f 1) (q 7 8))", "()", "7", 200),
+            // One quote, SHA256_BASE_COST + SHA256_COST_PER_ARG + 3 bytes * 2, and 32 bytes
+            // of result.
+            (
+                "(sha256 (q . \"abc\"))",
+                "()",
+                "0xba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+                567,
+            ),
+            ("(i (q . 1) (q . 10) (q . 20))", "()", "10", 93),
+            ("(c (q . 1) (q 2 3))", "()", "(q 2 3)", 90),
+        ];
+        for (source, env, output, cost) in cases {
+            let (actual_cost, actual) = run(source, env, MAX_BLOCK_COST_CLVM).unwrap();
+            assert_eq!(actual.disassemble(), output, "{}", source);
+            assert_eq!(actual_cost, cost, "{}", source);
+        }
+    }
+
+    #[test]
+    fn run_fails_on_raise() {
+        let error = run("(x (q . 1))", "()", MAX_BLOCK_COST_CLVM).unwrap_err();
+        assert!(error.to_string().contains("clvm raise"), "{}", error);
+    }
+
+    #[test]
+    fn run_fails_once_cost_exceeds_max_cost() {
+        assert!(run("(q . 42)", "()", 20).is_ok());
+        let error = run("(q . 42)", "()", 19).unwrap_err();
+        assert!(error.to_string().contains("cost exceeded"), "{}", error);
+        assert!(run("(+ (q . 2) (q . 3))", "()", 794).is_err());
+    }
+
+    #[test]
+    fn run_checks_cost_before_expensive_operators() {
+        let quoted = |bytes: Vec<u8>| Program::cons(Program::atom([1]), Program::atom(bytes));
+        let huge = || {
+            let mut bytes = vec![0x7f; 100_000];
+            bytes[99_999] = 0x01;
+            bytes
+        };
+        // Computing either of these would take far longer than the test is allowed to.
+        let programs = [
+            Program::list([
+                Program::atom([0x3c]),
+                quoted(huge()),
+                quoted(huge()),
+                quoted(huge()),
+            ]),
+            Program::list([
+                Program::atom([0x12]),
+                quoted(huge()),
+                quoted(huge()),
+                quoted(huge()),
+            ]),
+        ];
+        for program in programs {
+            let error = program.run(&Program::nil(), 1_000_000).unwrap_err();
+            assert!(error.to_string().contains("cost exceeded"), "{}", error);
+        }
+    }
+
+    #[test]
+    fn run_g1_operators() {
+        const GENERATOR: &str = "0x97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+        let output = |source: String| {
+            run(&source, "()", MAX_BLOCK_COST_CLVM)
+                .unwrap()
+                .1
+                .disassemble()
+        };
+        let g = format!("(q . {})", GENERATOR);
+        let identity = format!("0xc0{}", "00".repeat(47));
+
+        assert_eq!(
+            output(format!("(g1_multiply {} (q . 2))", g)),
+            output(format!("(point_add {} {})", g, g))
+        );
+        assert_eq!(
+            output(format!("(g1_multiply {} (q . -1))", g)),
+            output(format!("(g1_negate {})", g))
+        );
+        assert_eq!(
+            output(format!("(point_add {} (g1_negate {}))", g, g)),
+            identity
+        );
+        assert_eq!(
+            output(format!(
+                "(g1_subtract (pubkey_for_exp (q . 5)) {} {})",
+                g, g
+            )),
+            output("(pubkey_for_exp (q . 3))".to_string())
+        );
+        assert_eq!(output("(g1_subtract)".to_string()), identity);
+
+        // The first `hash_to_curve` vector for BLS12381G1_XMD:SHA-256_SSWU_RO_ in RFC 9380.
+        let (cost, point) = run(
+            "(g1_map (q . ()) (q . \"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_\"))",
+            "()",
+            MAX_BLOCK_COST_CLVM,
+        )
+        .unwrap();
+        assert_eq!(
+            point.disassemble(),
+            "0x852926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a1"
+        );
+        // Two quotes, G1_MAP_BASE_COST, 50 bytes of DST and 48 bytes of result.
+        assert_eq!(cost, 20 + 20 + 195_000 + 50 * 4 + 48 * 10);
+
+        let (cost, _) = run(&format!("(g1_negate {})", g), "()", MAX_BLOCK_COST_CLVM).unwrap();
+        assert_eq!(cost, 20 + 1_396 + 48 * 10);
+    }
+
+    #[test]
+    fn run_fails_on_unimplemented_operators() {
+        for source in [
+            "(g2_add)",
+            "(bls_verify (q . 1))",
+            "(bls_pairing_identity)",
+            "(secp256k1_verify (q . 1) (q . 2) (q . 3))",
+            "(secp256r1_verify (q . 1) (q . 2) (q . 3))",
+        ] {
+            let error = run(source, "()", MAX_BLOCK_COST_CLVM).unwrap_err();
+            assert!(
+                error.to_string().contains("unimplemented operator"),
+                "{}: {}",
+                source,
+                error
+            );
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use super::Program;
+use crate::{streamable::atom_length, Error};
+
+const CONS_BOX_MARKER: u8 = 0xff;
+const BACK_REFERENCE: u8 = 0xfe;
+
+impl Program {
+    /// Deserializes a program, including the back reference compression used by block
+    /// generators and newer wallets.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Program, Error> {
+        let (program, len) = Program::from_bytes_prefix(bytes)?;
+        if len != bytes.len() {
+            return Err(Error::ClvmError(format!(
+                "{} trailing bytes after serialized program",
+                bytes.len() - len
+            )));
+        }
+        Ok(program)
+    }
+
+    /// Deserializes the program at the start of `bytes`, returning it with the number of
+    /// bytes consumed.
+    pub fn from_bytes_prefix(bytes: &[u8]) -> Result<(Program, usize), Error> {
+        enum Op {
+            Parse,
+            Cons,
+        }
+
+        let truncated = || Error::ClvmError("truncated serialized program".to_string());
+        let mut position = 0;
+        let mut ops = vec![Op::Parse];
+        // Parsed values, most recent last. Back references address this stack as a list
+        // whose first item is the most recent value.
+        let mut values: Vec<Program> = Vec::new();
+        while let Some(op) = ops.pop() {
+            match op {
+                Op::Parse => {
+                    let b = *bytes.get(position).ok_or_else(truncated)?;
+                    match b {
+                        CONS_BOX_MARKER => {
+                            position += 1;
+                            ops.extend([Op::Cons, Op::Parse, Op::Parse]);
+                        },
+                        BACK_REFERENCE => {
+                            position += 1;
+                            let (path, len) =
+                                read_atom(&bytes[position..]).ok_or_else(truncated)?;
+                            position += len;
+                            values.push(traverse_stack(&values, &path)?);
+                        },
+                        _ => {
+                            let (atom, len) =
+                                read_atom(&bytes[position..]).ok_or_else(truncated)?;
+                            position += len;
+                            values.push(Program::Atom(atom));
+                        },
+                    }
+                },
+                Op::Cons => {
+                    let rest = values.pop().expect("rest value");
+                    let first = values.pop().expect("first value");
+                    values.push(Program::cons(first, rest));
+                },
+            }
+        }
+        Ok((values.pop().expect("parsed value"), position))
+    }
+
+    /// Serializes the program without back references.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        let mut stack = vec![self];
+        while let Some(program) = stack.pop() {
+            match program {
+                Program::Atom(atom) => write_atom(&mut out, atom),
+                Program::Pair(first, rest) => {
+                    out.push(CONS_BOX_MARKER);
+                    stack.push(rest);
+                    stack.push(first);
+                },
+            }
+        }
+        out
+    }
+}
+
+fn read_atom(bytes: &[u8]) -> Option<(Vec<u8>, usize)> {
+    let (size, header) = atom_length(bytes)?;
+    let end = header.checked_add(size)?;
+    if header == 0 {
+        return Some((vec![bytes[0]], 1));
+    }
+    Some((bytes.get(header..end)?.to_vec(), end))
+}
+
+fn write_atom(out: &mut Vec<u8>, atom: &[u8]) {
+    let len = atom.len();
+    match len {
+        0 => out.push(0x80),
+        1 if atom[0] <= 0x7f => {},
+        _ if len < 0x40 => out.push(0x80 | len as u8),
+        _ if len < 0x2000 => out.extend([0xc0 | (len >> 8) as u8, len as u8]),
+        _ if len < 0x10_0000 => out.extend([0xe0 | (len >> 16) as u8, (len >> 8) as u8, len as u8]),
+        _ if len < 0x800_0000 => out.extend([
+            0xf0 | (len >> 24) as u8,
+            (len >> 16) as u8,
+            (len >> 8) as u8,
+            len as u8,
+        ]),
+        _ => out.extend([
+            0xf8 | (len >> 32) as u8,
+            (len >> 24) as u8,
+            (len >> 16) as u8,
+            (len >> 8) as u8,
+            len as u8,
+        ]),
+    }
+    out.extend_from_slice(atom);
+}
+
+/// Follows a back reference path over the parse stack, viewed as a list of previously parsed
+/// values with the most recent first.
+fn traverse_stack(values: &[Program], path: &[u8]) -> Result<Program, Error> {
+    let bad_path = || Error::ClvmError("invalid back reference".to_string());
+    if path.iter().all(|b| *b == 0) {
+        return Ok(Program::nil());
+    }
+    let mut bits = path_bits(path).into_iter();
+    // Index into `values` of the head of the remaining stack list.
+    let mut index = values.len();
+    loop {
+        match bits.next() {
+            None => {
+                return Ok(values[..index]
+                    .iter()
+                    .cloned()
+                    .fold(Program::nil(), |rest, item| Program::cons(item, rest)));
+            },
+            Some(true) => index = index.checked_sub(1).ok_or_else(bad_path)?,
+            Some(false) => {
+                let mut current = values.get(index.wrapping_sub(1)).ok_or_else(bad_path)?;
+                for rest in bits {
+                    current = match (current, rest) {
+                        (Program::Pair(_, r), true) => r,
+                        (Program::Pair(f, _), false) => f,
+                        (Program::Atom(_), _) => return Err(bad_path()),
+                    };
+                }
+                return Ok(current.clone());
+            },
+        }
+    }
+}
+
+/// Decodes a CLVM path atom into navigation steps, `true` meaning "rest". Bits are consumed
+/// from the least significant end, up to but excluding the highest set bit.
+pub(crate) fn path_bits(path: &[u8]) -> Vec<bool> {
+    let Some(start) = path.iter().position(|b| *b != 0) else {
+        return Vec::new();
+    };
+    let path = &path[start..];
+    let mut bits = Vec::new();
+    for (i, byte) in path.iter().enumerate().rev() {
+        for bit in 0..8 {
+            if i == 0 && byte >> bit == 1 {
+                return bits;
+            }
+            bits.push((byte >> bit) & 1 == 1);
+        }
+    }
+    bits
+}
@@ -0,0 +1,336 @@
+use num_bigint::BigInt;
+
+use super::{atom_to_bigint, bigint_to_atom, Program};
+use crate::Error;
+
+/// Operator names used by `clvm_tools` when disassembling atoms in operator position.
+const KEYWORDS: &[(&str, &[u8])] = &[
+    ("q", &[0x01]),
+    ("a", &[0x02]),
+    ("i", &[0x03]),
+    ("c", &[0x04]),
+    ("f", &[0x05]),
+    ("r", &[0x06]),
+    ("l", &[0x07]),
+    ("x", &[0x08]),
+    ("=", &[0x09]),
+    (">s", &[0x0a]),
+    ("sha256", &[0x0b]),
+    ("substr", &[0x0c]),
+    ("strlen", &[0x0d]),
+    ("concat", &[0x0e]),
+    ("+", &[0x10]),
+    ("-", &[0x11]),
+    ("*", &[0x12]),
+    ("/", &[0x13]),
+    ("divmod", &[0x14]),
+    (">", &[0x15]),
+    ("ash", &[0x16]),
+    ("lsh", &[0x17]),
+    ("logand", &[0x18]),
+    ("logior", &[0x19]),
+    ("logxor", &[0x1a]),
+    ("lognot", &[0x1b]),
+    ("point_add", &[0x1d]),
+    ("pubkey_for_exp", &[0x1e]),
+    ("not", &[0x20]),
+    ("any", &[0x21]),
+    ("all", &[0x22]),
+    ("softfork", &[0x24]),
+    ("coinid", &[0x30]),
+    ("g1_subtract", &[0x31]),
+    ("g1_multiply", &[0x32]),
+    ("g1_negate", &[0x33]),
+    ("g2_add", &[0x34]),
+    ("g2_subtract", &[0x35]),
+    ("g2_multiply", &[0x36]),
+    ("g2_negate", &[0x37]),
+    ("g1_map", &[0x38]),
+    ("g2_map", &[0x39]),
+    ("bls_pairing_identity", &[0x3a]),
+    ("bls_verify", &[0x3b]),
+    ("modpow", &[0x3c]),
+    ("%", &[0x3d]),
+    ("secp256k1_verify", &[0x13, 0xd6, 0x1f, 0x00]),
+    ("secp256r1_verify", &[0x1c, 0x3a, 0x8f, 0x00]),
+];
+
+fn keyword_for_atom(atom: &[u8]) -> Option<&'static str> {
+    KEYWORDS
+        .iter()
+        .find(|(_, op)| *op == atom)
+        .map(|(name, _)| *name)
+}
+
+fn atom_for_keyword(keyword: &str) -> Option<&'static [u8]> {
+    KEYWORDS
+        .iter()
+        .find(|(name, _)| *name == keyword)
+        .map(|(_, op)| *op)
+}
+
+impl Program {
+    /// Renders the program the way `clvm_tools`' `opd` does: operators by name, short
+    /// canonical atoms as integers, printable atoms as strings and anything else as hex.
+    pub fn disassemble(&self) -> String {
+        let mut out = String::new();
+        write_program(self, false, &mut out);
+        out
+    }
+
+    /// Parses the `clvm_tools` text form accepted by `opc`. Operator names are resolved
+    /// anywhere they appear, and unknown symbols become their UTF-8 bytes.
+    pub fn assemble(source: &str) -> Result<Program, Error> {
+        let mut tokens = Tokenizer::new(source);
+        let program = match tokens.next() {
+            Some(token) => parse_sexp(token, &mut tokens)?,
+            None => return Err(Error::ClvmError("unexpected end of source".to_string())),
+        };
+        match tokens.next() {
+            Some((token, offset)) => Err(Error::ClvmError(format!(
+                "unexpected token {} at {}",
+                token, offset
+            ))),
+            None => Ok(program),
+        }
+    }
+}
+
+/// Writes the program using a stack of pending output rather than recursion, since
+/// programs can nest deeper than the call stack allows.
+fn write_program(program: &Program, operator_position: bool, out: &mut String) {
+    enum Pending<'a> {
+        Program(&'a Program, bool),
+        /// The rest of a list whose opening parenthesis and first item are written.
+        Tail(&'a Program),
+        Close,
+    }
+
+    let mut pending = vec![Pending::Program(program, operator_position)];
+    while let Some(next) = pending.pop() {
+        match next {
+            Pending::Program(Program::Atom(atom), operator_position) => {
+                write_atom(atom, operator_position, out)
+            },
+            Pending::Program(Program::Pair(first, rest), _) => {
+                out.push('(');
+                pending.push(Pending::Tail(rest));
+                pending.push(Pending::Program(first, true));
+            },
+            Pending::Tail(Program::Pair(first, rest)) => {
+                out.push(' ');
+                pending.push(Pending::Tail(rest));
+                pending.push(Pending::Program(first, false));
+            },
+            Pending::Tail(tail) if tail.is_nil() => out.push(')'),
+            Pending::Tail(tail) => {
+                out.push_str(" . ");
+                pending.push(Pending::Close);
+                pending.push(Pending::Program(tail, false));
+            },
+            Pending::Close => out.push(')'),
+        }
+    }
+}
+
+fn write_atom(atom: &[u8], operator_position: bool, out: &mut String) {
+    if atom.is_empty() {
+        out.push_str("()");
+        return;
+    }
+    if operator_position {
+        if let Some(keyword) = keyword_for_atom(atom) {
+            out.push_str(keyword);
+            return;
+        }
+    }
+    if atom.len() > 2 {
+        let printable = atom
+            .iter()
+            .all(|b| b.is_ascii_graphic() || b" \t\n\r\x0b\x0c".contains(b));
+        if printable {
+            let text = std::str::from_utf8(atom).expect("ascii atom");
+            if !text.contains('"') {
+                out.push_str(&format!("\"{}\"", text));
+                return;
+            }
+            if !text.contains('\'') {
+                out.push_str(&format!("'{}'", text));
+                return;
+            }
+        }
+    } else if bigint_to_atom(&atom_to_bigint(atom)) == atom {
+        out.push_str(&atom_to_bigint(atom).to_string());
+        return;
+    }
+    out.push_str("0x");
+    out.push_str(&hex::encode(atom));
+}
+
+struct Tokenizer<'a> {
+    source: &'a str,
+    offset: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn new(source: &'a str) -> Self {
+        Self { source, offset: 0 }
+    }
+
+    fn skip_whitespace(&mut self) {
+        let bytes = self.source.as_bytes();
+        while self.offset < bytes.len() {
+            match bytes[self.offset] {
+                b';' => {
+                    while self.offset < bytes.len() && bytes[self.offset] != b'\n' {
+                        self.offset += 1;
+                    }
+                },
+                b if b.is_ascii_whitespace() || b == 0x0b => self.offset += 1,
+                _ => break,
+            }
+        }
+    }
+
+    fn expect_next(&mut self) -> Result<(&'a str, usize), Error> {
+        self.next()
+            .ok_or_else(|| Error::ClvmError("unexpected end of source".to_string()))
+    }
+}
+
+impl<'a> Iterator for Tokenizer<'a> {
+    type Item = (&'a str, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.skip_whitespace();
+        let bytes = self.source.as_bytes();
+        let start = self.offset;
+        let c = *bytes.get(start)?;
+        if matches!(c, b'(' | b')' | b'.') {
+            self.offset += 1;
+        } else if matches!(c, b'"' | b'\'') {
+            self.offset += 1;
+            while self.offset < bytes.len() && bytes[self.offset] != c {
+                self.offset += 1;
+            }
+            // An unterminated string is reported by the parser.
+            self.offset = (self.offset + 1).min(bytes.len());
+        } else {
+            while self.offset < bytes.len()
+                && !bytes[self.offset].is_ascii_whitespace()
+                && bytes[self.offset] != b')'
+            {
+                self.offset += 1;
+            }
+        }
+        Some((&self.source[start..self.offset], start))
+    }
+}
+
+/// Parses one expression starting at `current`. Open lists are kept on a heap stack so
+/// deeply nested source cannot overflow the call stack.
+fn parse_sexp<'a>(
+    mut current: (&'a str, usize),
+    tokens: &mut Tokenizer<'a>,
+) -> Result<Program, Error> {
+    struct OpenList {
+        items: Vec<Program>,
+        /// Whether a `.` was read, making the next expression the tail of the list.
+        dotted: bool,
+    }
+
+    let mut lists: Vec<OpenList> = Vec::new();
+    loop {
+        let (token, offset) = current;
+        let mut value = match (token, lists.last_mut()) {
+            ("(", _) => {
+                lists.push(OpenList {
+                    items: Vec::new(),
+                    dotted: false,
+                });
+                current = tokens.expect_next()?;
+                continue;
+            },
+            (")", Some(list)) if !list.dotted => {
+                Program::list(lists.pop().expect("open list").items)
+            },
+            (".", Some(list)) if !list.dotted && !list.items.is_empty() => {
+                list.dotted = true;
+                current = tokens.expect_next()?;
+                continue;
+            },
+            (")" | ".", _) => {
+                return Err(Error::ClvmError(format!(
+                    "unexpected {} at {}",
+                    token, offset
+                )))
+            },
+            _ => parse_atom(token, offset)?,
+        };
+        // Hand the value to the innermost open list, closing each list it is the tail of.
+        loop {
+            match lists.last_mut() {
+                None => return Ok(value),
+                Some(list) if list.dotted => {
+                    let (close, close_offset) = tokens.expect_next()?;
+                    if close != ")" {
+                        return Err(Error::ClvmError(format!(
+                            "illegal dot expression at {}",
+                            close_offset
+                        )));
+                    }
+                    value = lists
+                        .pop()
+                        .expect("open list")
+                        .items
+                        .into_iter()
+                        .rev()
+                        .fold(value, |rest, item| Program::cons(item, rest));
+                },
+                Some(list) => {
+                    list.items.push(value);
+                    break;
+                },
+            }
+        }
+        current = tokens.expect_next()?;
+    }
+}
+
+fn parse_atom(token: &str, offset: usize) -> Result<Program, Error> {
+    let digits = token.strip_prefix(['-', '+']).unwrap_or(token);
+    if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+        let value: BigInt = token
+            .trim_start_matches('+')
+            .parse()
+            .map_err(|_| Error::ClvmError(format!("invalid integer at {}", offset)))?;
+        return Ok(Program::from_int(value));
+    }
+    if let Some(hex) = token
+        .strip_prefix("0x")
+        .or_else(|| token.strip_prefix("0X"))
+    {
+        let hex = if hex.len() % 2 == 1 {
+            format!("0{}", hex)
+        } else {
+            hex.to_string()
+        };
+        return hex::decode(&hex)
+            .map(Program::Atom)
+            .map_err(|_| Error::ClvmError(format!("invalid hex at {}: 0x{}", offset, hex)));
+    }
+    if let Some(quote) = token.chars().next().filter(|c| *c == '"' || *c == '\'') {
+        return match token[1..].strip_suffix(quote) {
+            Some(text) => Ok(Program::atom(text.as_bytes())),
+            None => Err(Error::ClvmError(format!(
+                "unterminated string starting at {}",
+                offset
+            ))),
+        };
+    }
+    let symbol = token.strip_prefix('#').unwrap_or(token);
+    Ok(match atom_for_keyword(symbol) {
+        Some(op) => Program::atom(op),
+        None => Program::atom(token.as_bytes()),
+    })
+}
@@ -0,0 +1,281 @@
+//! Client-side planning of dust consolidation: grouping a wallet's small coins into
+//! `combine_coins` transactions that each stay under a cost limit, with fee estimates.
+
+use crate::{
+    clvm::{CREATE_COIN_COST, MAX_BLOCK_COST_CLVM},
+    models::{fullnode::Coin, wallet::CombineCoinsOptions},
+    types::{CoinId, Mojos},
+};
+
+/// What each byte of a spend bundle adds to its cost.
+pub const COST_PER_BYTE: u64 = 12_000;
+/// The most cost a single spend bundle may have to be accepted by the mempool.
+pub const MAX_SPEND_BUNDLE_COST: u64 = MAX_BLOCK_COST_CLVM / 2;
+/// The most coins the wallet combines in one `combine_coins` call.
+pub const MAX_COMBINE_COINS: usize = 500;
+
+/// The cost the full node assumes for a standard send (one input, a payment and change) when
+/// estimating fees.
+const STANDARD_SEND_COST: u64 = 9_401_710;
+/// The approximate size of a `(51 puzzle_hash amount)` condition in a delegated puzzle.
+const STANDARD_OUTPUT_BYTES: u64 = 45;
+const STANDARD_OUTPUT_COST: u64 = CREATE_COIN_COST + STANDARD_OUTPUT_BYTES * COST_PER_BYTE;
+
+/// Estimated costs of the parts of a transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpendCosts {
+    /// Spending one coin: its puzzle reveal and solution, running them, and its signature.
+    pub per_input: u64,
+    /// Creating one coin.
+    pub per_output: u64,
+}
+
+/// Costs of standard XCH coins, derived from the full node's estimate for a standard send.
+pub const STANDARD_SPEND_COSTS: SpendCosts = SpendCosts {
+    per_input: STANDARD_SEND_COST - 2 * STANDARD_OUTPUT_COST,
+    per_output: STANDARD_OUTPUT_COST,
+};
+
+impl SpendCosts {
+    /// The estimated cost of a transaction spending `inputs` coins into `outputs` coins.
+    pub fn estimate(&self, inputs: usize, outputs: usize) -> u64 {
+        self.per_input * inputs as u64 + self.per_output * outputs as u64
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ConsolidationOptions {
+    /// The most cost one transaction may have.
+    pub max_cost: u64,
+    /// The most coins one transaction may spend. Batches never exceed `u16::MAX` coins, the
+    /// most `combine_coins` accepts.
+    pub max_coins: usize,
+    /// Only coins of at most this amount are consolidated.
+    pub max_coin_amount: Option<Mojos>,
+    /// The fee rate in mojos per unit of cost, as reported by the full node's fee estimates.
+    pub fee_per_cost: f64,
+    pub costs: SpendCosts,
+}
+
+impl Default for ConsolidationOptions {
+    fn default() -> Self {
+        Self {
+            max_cost: MAX_SPEND_BUNDLE_COST,
+            max_coins: MAX_COMBINE_COINS,
+            max_coin_amount: None,
+            fee_per_cost: 0.0,
+            costs: STANDARD_SPEND_COSTS,
+        }
+    }
+}
+
+impl ConsolidationOptions {
+    /// The fee for a transaction of `cost`, rounded up to a whole mojo.
+    pub fn fee_for_cost(&self, cost: u64) -> Mojos {
+        Mojos((cost as f64 * self.fee_per_cost).ceil() as u64)
+    }
+}
+
+/// One planned transaction, combining its coins into a single coin.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ConsolidationBatch {
+    pub coins: Vec<Coin>,
+    /// The total amount of the coins.
+    pub amount: Mojos,
+    pub estimated_cost: u64,
+    pub fee: Mojos,
+}
+
+impl ConsolidationBatch {
+    pub fn coin_ids(&self) -> Vec<CoinId> {
+        self.coins.iter().map(Coin::coin_id).collect()
+    }
+
+    /// The amount of the combined coin, after the fee.
+    pub fn output_amount(&self) -> Mojos {
+        self.amount.checked_sub(self.fee).unwrap_or(Mojos::ZERO)
+    }
+
+    /// Options for [`crate::wallet::Rpc::combine_coins`] that combine exactly these coins. Pass
+    /// [`ConsolidationBatch::fee`] as the fee. A batch of more than `u16::MAX` coins, which
+    /// [`plan_consolidation`] never makes, asks for `u16::MAX`.
+    pub fn combine_options(&self) -> CombineCoinsOptions {
+        CombineCoinsOptions {
+            number_of_coins: Some(u16::try_from(self.coins.len()).unwrap_or(u16::MAX)),
+            target_coin_ids: self.coin_ids(),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ConsolidationPlan {
+    pub batches: Vec<ConsolidationBatch>,
+    /// Coins left alone: those worth less than the fee to spend them, and a last coin that
+    /// would have no other coin to combine with.
+    pub skipped: Vec<Coin>,
+}
+
+impl ConsolidationPlan {
+    pub fn total_fee(&self) -> Mojos {
+        self.batches.iter().map(|batch| batch.fee).sum()
+    }
+
+    pub fn total_cost(&self) -> u64 {
+        self.batches.iter().map(|batch| batch.estimated_cost).sum()
+    }
+
+    /// The number of coins the plan spends.
+    pub fn coin_count(&self) -> usize {
+        self.batches.iter().map(|batch| batch.coins.len()).sum()
+    }
+}
+
+/// Plans transactions that combine `coins`, smallest first, so that each spends as many coins
+/// as `options` allow. Each batch's fee is paid from the coins it combines.
+pub fn plan_consolidation(coins: &[Coin], options: &ConsolidationOptions) -> ConsolidationPlan {
+    let mut plan = ConsolidationPlan::default();
+    let input_fee = options.fee_for_cost(options.costs.per_input);
+    let mut candidates = Vec::new();
+    for coin in coins {
+        if options
+            .max_coin_amount
+            .is_some_and(|max_amount| coin.amount > max_amount)
+        {
+            continue;
+        }
+        if coin.amount <= input_fee {
+            plan.skipped.push(coin.clone());
+        } else {
+            candidates.push(coin.clone());
+        }
+    }
+    candidates.sort_by_key(|coin| coin.amount);
+
+    let max_inputs = options
+        .max_cost
+        .saturating_sub(options.costs.per_output)
+        .checked_div(options.costs.per_input)
+        .map_or(usize::MAX, |inputs| inputs as usize)
+        .min(options.max_coins)
+        .min(usize::from(u16::MAX));
+    if max_inputs < 2 {
+        plan.skipped.extend(candidates);
+        return plan;
+    }
+    for chunk in candidates.chunks(max_inputs) {
+        if chunk.len() < 2 {
+            plan.skipped.extend_from_slice(chunk);
+            continue;
+        }
+        let estimated_cost = options.costs.estimate(chunk.len(), 1);
+        plan.batches.push(ConsolidationBatch {
+            coins: chunk.to_vec(),
+            amount: chunk.iter().map(|coin| coin.amount).sum(),
+            estimated_cost,
+            fee: options.fee_for_cost(estimated_cost),
+        });
+    }
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::PuzzleHash;
+
+    /// Costs that make the fee to spend one coin 100 mojos at one mojo per cost.
+    const OPTIONS: ConsolidationOptions = ConsolidationOptions {
+        max_cost: MAX_SPEND_BUNDLE_COST,
+        max_coins: MAX_COMBINE_COINS,
+        max_coin_amount: None,
+        fee_per_cost: 1.0,
+        costs: SpendCosts {
+            per_input: 100,
+            per_output: 10,
+        },
+    };
+
+    fn coins(amounts: &[u64]) -> Vec<Coin> {
+        amounts
+            .iter()
+            .enumerate()
+            .map(|(i, amount)| Coin {
+                amount: Mojos(*amount),
+                parent_coin_info: CoinId::new([i as u8; 32]),
+                puzzle_hash: PuzzleHash::new([0x11; 32]),
+            })
+            .collect()
+    }
+
+    fn amounts(coins: &[Coin]) -> Vec<u64> {
+        coins.iter().map(|coin| coin.amount.0).collect()
+    }
+
+    #[test]
+    fn plan_skips_dust_and_filters_large_coins() {
+        let options = ConsolidationOptions {
+            max_coin_amount: Some(Mojos(1000)),
+            ..OPTIONS
+        };
+        let plan = plan_consolidation(&coins(&[200, 50, 5000, 150, 100]), &options);
+
+        assert_eq!(amounts(&plan.skipped), [50, 100]);
+        assert_eq!(plan.batches.len(), 1);
+        assert_eq!(amounts(&plan.batches[0].coins), [150, 200]);
+        assert_eq!(plan.batches[0].amount, Mojos(350));
+        assert_eq!(plan.batches[0].estimated_cost, 210);
+        assert_eq!(plan.batches[0].fee, Mojos(210));
+        assert_eq!(plan.batches[0].output_amount(), Mojos(140));
+    }
+
+    #[test]
+    fn plan_skips_a_trailing_single_coin() {
+        let options = ConsolidationOptions {
+            max_coins: 2,
+            ..OPTIONS
+        };
+        let plan = plan_consolidation(&coins(&[103, 101, 102]), &options);
+
+        assert_eq!(plan.batches.len(), 1);
+        assert_eq!(amounts(&plan.batches[0].coins), [101, 102]);
+        assert_eq!(amounts(&plan.skipped), [103]);
+        assert_eq!(plan.coin_count(), 2);
+    }
+
+    #[test]
+    fn plan_skips_everything_when_a_batch_cannot_hold_two_coins() {
+        let options = ConsolidationOptions {
+            max_cost: 210 - 1,
+            ..OPTIONS
+        };
+        let plan = plan_consolidation(&coins(&[50, 300, 200]), &options);
+
+        assert!(plan.batches.is_empty());
+        assert_eq!(amounts(&plan.skipped), [50, 200, 300]);
+        assert_eq!(plan.total_fee(), Mojos::ZERO);
+    }
+
+    #[test]
+    fn plan_batches_fit_combine_coins() {
+        let options = ConsolidationOptions {
+            max_cost: u64::MAX,
+            max_coins: usize::MAX,
+            ..OPTIONS
+        };
+        let coins = vec![coins(&[1000])[0].clone(); usize::from(u16::MAX) + 2];
+        let plan = plan_consolidation(&coins, &options);
+
+        assert_eq!(plan.batches.len(), 2);
+        assert_eq!(plan.batches[0].coins.len(), usize::from(u16::MAX));
+        assert_eq!(
+            plan.batches[0].combine_options().number_of_coins,
+            Some(u16::MAX)
+        );
+
+        let batch = &plan.batches[1];
+        let combine = batch.combine_options();
+        assert_eq!(combine.number_of_coins, Some(2));
+        assert_eq!(combine.target_coin_ids, batch.coin_ids());
+    }
+}
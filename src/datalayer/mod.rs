@@ -1,3 +1,5 @@
+pub mod proof;
+
 use crate::prelude::*;
 
 #[derive(Clone)]
@@ -9,7 +11,13 @@ impl Rpc {
         Self { client }
     }
 
-    pub async fn add_mirror(&self, id: &str, urls: Vec<&str>, amount: u64, fee: u64) -> Result<()> {
+    pub async fn add_mirror(
+        &self,
+        id: &Bytes32,
+        urls: Vec<&str>,
+        amount: Mojos,
+        fee: Mojos,
+    ) -> Result<()> {
         let json = json!({
             "id": id,
             "urls": urls,
@@ -41,7 +49,7 @@ impl Rpc {
         }
     }
 
-    pub async fn get_owned_stores(&self) -> Result<Vec<String>> {
+    pub async fn get_owned_stores(&self) -> Result<Vec<Bytes32>> {
         let res: StoresResponse = self
             .client
             .cmd("get_owned_stores", None)
@@ -54,15 +62,6 @@ impl Rpc {
         }
     }
 
-    pub async fn close_connection(&self) -> Result<Vec<String>> {
-        Ok(self
-            .client
-            .cmd("close_connection", None)
-            .await?
-            .json()
-            .await?)
-    }
-
     pub async fn get_connections(&self) -> Result<Vec<Connection>> {
         let res: ConnectionsResponse = self
             .client
@@ -76,10 +75,10 @@ impl Rpc {
         }
     }
 
-    pub async fn open_connection(&self, host: &str, port: u64) -> Result<()> {
+    pub async fn open_connection(&self, host: &str, port: u16) -> Result<()> {
         let json = json!({
             "host": host,
-            "port": port.to_string(),
+            "port": port,
         });
         let res: BasicResponse = self
             .client
@@ -93,6 +92,22 @@ impl Rpc {
         }
     }
 
+    pub async fn close_connection(&self, node_id: &str) -> Result<()> {
+        let json = json!({
+            "node_id": node_id,
+        });
+        let res: BasicResponse = self
+            .client
+            .cmd("close_connection", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.error {
+            Some(e) => Err(anyhow!("{:#?}", e)),
+            None => Ok(()),
+        }
+    }
+
     pub async fn stop_node(&self) -> Result<()> {
         let res: BasicResponse = self.client.cmd("stop_node", None).await?.json().await?;
         match res.error {
@@ -111,10 +126,10 @@ impl Rpc {
 
     pub async fn batch_update(
         &self,
-        store_id: &str,
+        store_id: &Bytes32,
         changelist: Vec<Changelist>,
-        fee: u64,
-    ) -> Result<String> {
+        fee: Mojos,
+    ) -> Result<Bytes32> {
         let json = json!({
             "id": store_id,
             "changelist": changelist,
@@ -132,7 +147,7 @@ impl Rpc {
         }
     }
 
-    pub async fn cancel_offer(&self, trade_id: &str, secure: bool, fee: u64) -> Result<()> {
+    pub async fn cancel_offer(&self, trade_id: &Bytes32, secure: bool, fee: Mojos) -> Result<()> {
         let json = json!({
             "trade_id": trade_id,
             "secure": secure,
@@ -150,7 +165,7 @@ impl Rpc {
         }
     }
 
-    pub async fn create_data_store(&self, fee: u64) -> Result<CreateDataStoreResponse> {
+    pub async fn create_data_store(&self, fee: Mojos) -> Result<CreateDataStoreResponse> {
         let json = json!({
             "fee": fee.to_string(),
         });
@@ -166,7 +181,7 @@ impl Rpc {
         }
     }
 
-    pub async fn delete_key(&self, id: &str, key: &str, fee: u64) -> Result<String> {
+    pub async fn delete_key(&self, id: &Bytes32, key: &str, fee: Mojos) -> Result<Bytes32> {
         let json = json!({
             "id": id,
             "key": key,
@@ -184,7 +199,7 @@ impl Rpc {
         }
     }
 
-    pub async fn delete_mirror(&self, id: &str) -> Result<()> {
+    pub async fn delete_mirror(&self, id: &Bytes32) -> Result<()> {
         let json = json!({
             "id": id,
         });
@@ -199,7 +214,7 @@ impl Rpc {
             None => Ok(()),
         }
     }
-    pub async fn get_root(&self, id: &str) -> Result<RootResponse> {
+    pub async fn get_root(&self, id: &Bytes32) -> Result<RootResponse> {
         let json = json!({
             "id": id,
         });
@@ -214,7 +229,7 @@ impl Rpc {
             None => Err(anyhow!("{:#?}", res.error)),
         }
     }
-    pub async fn get_root_history(&self, id: &str) -> Result<Vec<RootHistory>> {
+    pub async fn get_root_history(&self, id: &Bytes32) -> Result<Vec<RootHistory>> {
         let json = json!({
             "id": id,
         });
@@ -230,7 +245,7 @@ impl Rpc {
         }
     }
 
-    pub async fn get_roots(&self, ids: Vec<&str>) -> Result<Vec<RootHash>> {
+    pub async fn get_roots(&self, ids: &[Bytes32]) -> Result<Vec<RootHash>> {
         let json = json!({
             "ids": ids,
         });
@@ -246,7 +261,43 @@ impl Rpc {
         }
     }
 
-    pub async fn get_ancestors(&self, id: &str, hash: &str) -> Result<Vec<String>> {
+    /// Asks the node for inclusion proofs of `keys` (hex) in the store's current root. Check
+    /// them with [`DlProof::verify`] or [`Rpc::verify_proof`].
+    pub async fn get_proof(&self, store_id: &Bytes32, keys: &[&str]) -> Result<DlProof> {
+        let json = json!({
+            "store_id": store_id,
+            "keys": keys,
+        });
+        let res: GetProofResponse = self
+            .client
+            .cmd("get_proof", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.proof {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Has the node check a proof against the store's coin on chain.
+    pub async fn verify_proof(&self, proof: &DlProof) -> Result<VerifiedProof> {
+        let res: VerifyProofResponse = self
+            .client
+            .cmd("verify_proof", Some(serde_json::to_string(proof)?))
+            .await?
+            .json()
+            .await?;
+        match (res.verified_clvm_hashes, res.current_root) {
+            (Some(verified_clvm_hashes), Some(current_root)) => Ok(VerifiedProof {
+                verified_clvm_hashes,
+                current_root,
+            }),
+            _ => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn get_ancestors(&self, id: &Bytes32, hash: &Bytes32) -> Result<Vec<String>> {
         let json = json!({
             "id": id,
             "hash": hash,
@@ -263,7 +314,7 @@ impl Rpc {
         }
     }
 
-    pub async fn get_keys(&self, id: &str, root_hash: &str) -> Result<Vec<String>> {
+    pub async fn get_keys(&self, id: &Bytes32, root_hash: &Bytes32) -> Result<Vec<String>> {
         let json = json!({
             "id": id,
             "root_hash": root_hash,
@@ -280,7 +331,12 @@ impl Rpc {
         }
     }
 
-    pub async fn get_kv_diff(&self, id: &str, hash_1: &str, hash_2: &str) -> Result<Vec<Diff>> {
+    pub async fn get_kv_diff(
+        &self,
+        id: &Bytes32,
+        hash_1: &Bytes32,
+        hash_2: &Bytes32,
+    ) -> Result<Vec<Diff>> {
         let json = json!({
             "id": id,
             "hash_1": hash_1,
@@ -298,7 +354,7 @@ impl Rpc {
         }
     }
 
-    pub async fn get_local_root(&self, id: &str) -> Result<String> {
+    pub async fn get_local_root(&self, id: &Bytes32) -> Result<Bytes32> {
         let json = json!({
             "id": id,
         });
@@ -314,7 +370,7 @@ impl Rpc {
         }
     }
 
-    pub async fn get_mirrors(&self, id: &str) -> Result<Vec<Mirror>> {
+    pub async fn get_mirrors(&self, id: &Bytes32) -> Result<Vec<Mirror>> {
         let json = json!({
             "id": id,
         });
@@ -330,7 +386,7 @@ impl Rpc {
         }
     }
 
-    pub async fn get_sync_status(&self, store_id: &str) -> Result<SyncStatus> {
+    pub async fn get_sync_status(&self, store_id: &Bytes32) -> Result<SyncStatus> {
         let json = json!({
             "id": store_id,
         });
@@ -348,13 +404,15 @@ impl Rpc {
 
     pub async fn get_keys_values(
         &self,
-        id: &str,
-        root_hash: Option<&str>,
+        id: &Bytes32,
+        root_hash: Option<&Bytes32>,
     ) -> Result<Vec<KeysValue>> {
-        let json = json!({
+        let mut json = json!({
             "id": id,
-            "root_hash": root_hash.unwrap_or(""),
         });
+        if let Some(root_hash) = root_hash {
+            json["root_hash"] = json!(root_hash);
+        }
         let res: KeysValuesResponse = self
             .client
             .cmd("get_keys_values", Some(json.to_string()))
@@ -367,7 +425,7 @@ impl Rpc {
         }
     }
 
-    pub async fn get_value(&self, id: &str, key: &str, root_hash: &str) -> Result<String> {
+    pub async fn get_value(&self, id: &Bytes32, key: &str, root_hash: &Bytes32) -> Result<String> {
         let json = json!({
             "id": id,
             "key": key,
@@ -385,7 +443,13 @@ impl Rpc {
             Some(r) => Ok(r),
         }
     }
-    pub async fn insert(&self, store_id: &str, key: &str, value: &str, fee: u64) -> Result<String> {
+    pub async fn insert(
+        &self,
+        store_id: &Bytes32,
+        key: &str,
+        value: &str,
+        fee: Mojos,
+    ) -> Result<Bytes32> {
         let json = json!({
             "id": store_id,
             "key": key,
@@ -416,7 +480,7 @@ impl Rpc {
             Some(r) => Ok(r),
         }
     }
-    pub async fn take_offer(&self, offer: Offer) -> Result<String> {
+    pub async fn take_offer(&self, offer: Offer) -> Result<Bytes32> {
         let res: TakeOfferResponse = self
             .client
             .cmd("take_offer", Some(serde_json::to_string(&offer)?))
@@ -436,7 +500,7 @@ impl Rpc {
             .json()
             .await?)
     }
-    pub async fn remove_subscriptions(&self, id: &str, urls: Vec<&str>) -> Result<()> {
+    pub async fn remove_subscriptions(&self, id: &Bytes32, urls: Vec<&str>) -> Result<()> {
         let json = json!({
             "id": id,
             "urls": urls,
@@ -454,7 +518,7 @@ impl Rpc {
         }
     }
 
-    pub async fn subscribe(&self, id: &str, urls: Vec<&str>) -> Result<()> {
+    pub async fn subscribe(&self, id: &Bytes32, urls: Vec<&str>) -> Result<()> {
         let json = json!({
             "id": id,
             "urls": urls,
@@ -471,14 +535,14 @@ impl Rpc {
             None => Ok(()),
         }
     }
-    pub async fn unsubscribe(&self, id: &str) -> Result<()> {
+    pub async fn unsubscribe(&self, id: &Bytes32) -> Result<()> {
         let json = json!({
             "id": id,
         });
 
         let res: BasicResponse = self
             .client
-            .cmd("subscribe", Some(json.to_string()))
+            .cmd("unsubscribe", Some(json.to_string()))
             .await?
             .json()
             .await?;
@@ -487,7 +551,7 @@ impl Rpc {
             None => Ok(()),
         }
     }
-    pub async fn subscriptions(&self) -> Result<Vec<String>> {
+    pub async fn subscriptions(&self) -> Result<Vec<Bytes32>> {
         let res: StoresResponse = self.client.cmd("subscriptions", None).await?.json().await?;
         match res.store_ids {
             None => Err(anyhow!("{:#?}", res.error)),
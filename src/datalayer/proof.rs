@@ -0,0 +1,267 @@
+//! Offline verification of DataLayer inclusion proofs. A store is a Merkle tree whose leaves
+//! hash like the CLVM pair `(key . value)` and whose internal nodes are `sha256(2 + left +
+//! right)`, so a value can be checked against a root hash without trusting the node serving
+//! it.
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    clvm::Program,
+    models::datalayer::{
+        DlProof, HashOnlyProof, KeyValueHashes, Proof, ProofResultInclusions, Side,
+    },
+    types::Bytes32,
+    util::decode_hex,
+    Error,
+};
+
+/// The CLVM tree hash of a key or value, `sha256(1 + bytes)`.
+pub fn clvm_hash(bytes: &[u8]) -> Bytes32 {
+    Program::atom(bytes).tree_hash()
+}
+
+/// The hash of a leaf holding `key` and `value`.
+pub fn leaf_hash(key: &[u8], value: &[u8]) -> Bytes32 {
+    internal_hash(&clvm_hash(key), &clvm_hash(value))
+}
+
+/// The hash of a node with the given children, which is also the tree hash of the CLVM pair
+/// of two programs with these tree hashes.
+pub fn internal_hash(left: &Bytes32, right: &Bytes32) -> Bytes32 {
+    let mut hasher = Sha256::new();
+    hasher.update([2]);
+    hasher.update(left.as_bytes());
+    hasher.update(right.as_bytes());
+    Bytes32::new(hasher.finalize().into())
+}
+
+/// The hash of the parent of `hash`, given its sibling and the side the sibling is on.
+pub fn parent_hash(hash: &Bytes32, other_hash_side: Side, other_hash: &Bytes32) -> Bytes32 {
+    match other_hash_side {
+        Side::Left => internal_hash(other_hash, hash),
+        Side::Right => internal_hash(hash, other_hash),
+    }
+}
+
+/// Walks up from `node_hash`, checking each layer's combined hash, and returns the root.
+fn root_from_layers<'a>(
+    node_hash: Bytes32,
+    layers: impl Iterator<Item = (Side, &'a Bytes32, &'a Bytes32)>,
+) -> Result<Bytes32, Error> {
+    layers
+        .enumerate()
+        .try_fold(node_hash, |hash, (index, (side, other, combined))| {
+            let parent = parent_hash(&hash, side, other);
+            if parent != *combined {
+                return Err(Error::InvalidProof(format!(
+                    "layer {} combines to {}, but claims {}",
+                    index, parent, combined
+                )));
+            }
+            Ok(parent)
+        })
+}
+
+fn check_root(computed: Bytes32, root_hash: &Bytes32) -> Result<(), Error> {
+    if computed != *root_hash {
+        return Err(Error::InvalidProof(format!(
+            "proof leads to root {}, expected {}",
+            computed, root_hash
+        )));
+    }
+    Ok(())
+}
+
+impl HashOnlyProof {
+    /// Recomputes the leaf from the key and value hashes and walks the layers up to the root.
+    pub fn root(&self) -> Result<Bytes32, Error> {
+        let leaf = internal_hash(&self.key_clvm_hash, &self.value_clvm_hash);
+        if leaf != self.node_hash {
+            return Err(Error::InvalidProof(format!(
+                "key and value hash to {}, but the proof claims {}",
+                leaf, self.node_hash
+            )));
+        }
+        root_from_layers(
+            leaf,
+            self.layers.iter().map(|layer| {
+                (
+                    layer.other_hash_side,
+                    &layer.other_hash,
+                    &layer.combined_hash,
+                )
+            }),
+        )
+    }
+
+    pub fn verify(&self, root_hash: &Bytes32) -> Result<(), Error> {
+        check_root(self.root()?, root_hash)
+    }
+
+    /// Whether this proof is for exactly `key` and `value`.
+    pub fn proves(&self, key: &[u8], value: &[u8]) -> bool {
+        self.key_clvm_hash == clvm_hash(key) && self.value_clvm_hash == clvm_hash(value)
+    }
+}
+
+impl Proof {
+    /// Recomputes the leaf from the key and value and walks the layers up to the root.
+    pub fn root(&self) -> Result<Bytes32, Error> {
+        let leaf = leaf_hash(&decode_hex(&self.key)?, &decode_hex(&self.value)?);
+        if leaf != self.node_hash {
+            return Err(Error::InvalidProof(format!(
+                "key and value hash to {}, but the proof claims {}",
+                leaf, self.node_hash
+            )));
+        }
+        root_from_layers(
+            leaf,
+            self.layers.iter().map(|layer| {
+                (
+                    layer.other_hash_side,
+                    &layer.other_hash,
+                    &layer.combined_hash,
+                )
+            }),
+        )
+    }
+
+    pub fn verify(&self, root_hash: &Bytes32) -> Result<(), Error> {
+        check_root(self.root()?, root_hash)
+    }
+}
+
+impl DlProof {
+    /// Checks every proof against `root_hash`, e.g. the store's hash from `get_root` or
+    /// `get_roots`, and returns what they include, like `verify_proof` does. A proof without
+    /// any keys proves nothing and is rejected. Unlike `verify_proof`, this does not check
+    /// that `coin_id` is the store's coin on chain.
+    pub fn verify(&self, root_hash: &Bytes32) -> Result<ProofResultInclusions, Error> {
+        if self.store_proofs.proofs.is_empty() {
+            return Err(Error::InvalidProof("proof includes no keys".to_string()));
+        }
+        let inclusions = self
+            .store_proofs
+            .proofs
+            .iter()
+            .map(|proof| {
+                proof.verify(root_hash)?;
+                Ok(KeyValueHashes {
+                    key_clvm_hash: proof.key_clvm_hash,
+                    value_clvm_hash: proof.value_clvm_hash,
+                })
+            })
+            .collect::<Result<_, Error>>()?;
+        Ok(ProofResultInclusions {
+            store_id: self.store_proofs.store_id,
+            inclusions,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use super::*;
+    use crate::models::datalayer::{GetProofResponse, Layer};
+
+    /// A `get_proof` response for keys `a` and `c` of a store holding `a: 1`, `b: 2` and
+    /// `c: 3` in the tree `((a b) c)`, with hashes computed independently.
+    const GET_PROOF: &str = include_str!("../../tests/fixtures/datalayer/get_proof.json");
+    const ROOT_HASH: Bytes32 = Bytes32::new(hex!(
+        "9c0426268ed623a0f568c97a11e88265fb13d883a5a2386e59a7a2fc0a3c162b"
+    ));
+
+    fn get_proof() -> DlProof {
+        serde_json::from_str::<GetProofResponse>(GET_PROOF)
+            .unwrap()
+            .proof
+            .unwrap()
+    }
+
+    fn assert_invalid(result: Result<ProofResultInclusions, Error>) {
+        assert!(
+            matches!(result, Err(Error::InvalidProof(_))),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn verify_get_proof_response() {
+        let proof = get_proof();
+        let inclusions = proof.verify(&ROOT_HASH).unwrap();
+        assert_eq!(inclusions.store_id, proof.store_proofs.store_id);
+        assert_eq!(inclusions.inclusions, [
+            KeyValueHashes {
+                key_clvm_hash: clvm_hash(b"a"),
+                value_clvm_hash: clvm_hash(b"1"),
+            },
+            KeyValueHashes {
+                key_clvm_hash: clvm_hash(b"c"),
+                value_clvm_hash: clvm_hash(b"3"),
+            },
+        ]);
+        assert!(proof.store_proofs.proofs[0].proves(b"a", b"1"));
+        assert!(!proof.store_proofs.proofs[0].proves(b"a", b"2"));
+    }
+
+    #[test]
+    fn verify_rejects_other_roots_and_tampered_layers() {
+        let proof = get_proof();
+        assert_invalid(proof.verify(&Bytes32::new([0; 32])));
+
+        let mut tampered = proof.clone();
+        tampered.store_proofs.proofs[0].layers[0].other_hash = Bytes32::new([0; 32]);
+        assert_invalid(tampered.verify(&ROOT_HASH));
+
+        let mut tampered = proof.clone();
+        tampered.store_proofs.proofs[1].value_clvm_hash = clvm_hash(b"4");
+        assert_invalid(tampered.verify(&ROOT_HASH));
+
+        let mut tampered = proof;
+        tampered.store_proofs.proofs[1].layers[0].other_hash_side = Side::Right;
+        assert_invalid(tampered.verify(&ROOT_HASH));
+    }
+
+    #[test]
+    fn verify_rejects_proofs_without_keys() {
+        let mut proof = get_proof();
+        proof.store_proofs.proofs.clear();
+        assert_invalid(proof.verify(&ROOT_HASH));
+    }
+
+    #[test]
+    fn verify_offer_proof() {
+        let a_and_b = internal_hash(&leaf_hash(b"a", b"1"), &leaf_hash(b"b", b"2"));
+        let proof = Proof {
+            key: hex::encode("b"),
+            value: hex::encode("2"),
+            node_hash: leaf_hash(b"b", b"2"),
+            layers: vec![
+                Layer {
+                    combined_hash: a_and_b,
+                    other_hash: leaf_hash(b"a", b"1"),
+                    other_hash_side: Side::Left,
+                },
+                Layer {
+                    combined_hash: ROOT_HASH,
+                    other_hash: leaf_hash(b"c", b"3"),
+                    other_hash_side: Side::Right,
+                },
+            ],
+        };
+        assert_eq!(proof.root().unwrap(), ROOT_HASH);
+        proof.verify(&ROOT_HASH).unwrap();
+
+        let tampered = Proof {
+            value: hex::encode("3"),
+            ..proof
+        };
+        assert!(matches!(
+            tampered.verify(&ROOT_HASH),
+            Err(Error::InvalidProof(_))
+        ));
+    }
+}
@@ -1,3 +1,7 @@
+use std::time::Duration;
+
+use crate::types::Bytes32;
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("I/O error:  {0}")]
@@ -10,4 +14,38 @@ pub enum Error {
     HexDecodingError(#[from] hex::FromHexError),
     #[error("Bit conversion error")]
     BitConversionError,
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+    #[error("Amount {0} has more than {1} decimal places")]
+    AmountPrecision(String, u32),
+    #[error("Amount {0} overflows a 64-bit mojo value")]
+    AmountOverflow(String),
+    #[error("Streamable error: {0}")]
+    StreamableError(String),
+    #[error("CLVM error: {0}")]
+    ClvmError(String),
+    #[error("Offer error: {0}")]
+    OfferError(String),
+    #[error("Address prefix {found} does not belong to this network, expected {expected}")]
+    WrongNetwork { expected: String, found: String },
+    #[error("Address {0} has an invalid checksum")]
+    InvalidAddressChecksum(String),
+    #[error("Address {0} is encoded as bech32, expected bech32m")]
+    InvalidAddressVariant(String),
+    #[error("Address prefix {0:?} is not a valid bech32 human readable part")]
+    InvalidAddressPrefix(String),
+    #[error("Address encodes {0} bytes, expected 32")]
+    InvalidAddressLength(usize),
+    #[error("BLS error: {0}")]
+    BlsError(String),
+    #[error("Invalid DataLayer proof: {0}")]
+    InvalidProof(String),
+    #[error("Transaction {0} was rejected: {1}")]
+    TransactionFailed(Bytes32, String),
+    #[error("Transaction {0} is no longer known to the wallet")]
+    TransactionRemoved(Bytes32),
+    #[error("Invalid confirmation options: {0}")]
+    InvalidConfirmationOptions(String),
+    #[error("Transaction {0} was not confirmed within {1:?}")]
+    ConfirmationTimeout(Bytes32, Duration),
 }
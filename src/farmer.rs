@@ -0,0 +1,71 @@
+use crate::prelude::*;
+
+#[derive(Clone)]
+pub struct Rpc {
+    pub client: Client,
+}
+
+impl Rpc {
+    pub fn init(client: Client) -> Self {
+        Self { client }
+    }
+
+    pub async fn get_routes(&self) -> Result<Vec<String>> {
+        let res: RoutesResponse = self.client.cmd("get_routes", None).await?.json().await?;
+        match res.routes {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn get_connections(&self) -> Result<Vec<Connection>> {
+        let res: ConnectionsResponse = self
+            .client
+            .cmd("get_connections", None)
+            .await?
+            .json()
+            .await?;
+        match res.connections {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn open_connection(&self, host: &str, port: u16) -> Result<()> {
+        let json = json!({
+            "host": host,
+            "port": port,
+        });
+        let res: BasicResponse = self
+            .client
+            .cmd("open_connection", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.error {
+            Some(e) => Err(anyhow!("{:#?}", e)),
+            None => Ok(()),
+        }
+    }
+
+    pub async fn close_connection(&self, node_id: &str) -> Result<()> {
+        let json = json!({
+            "node_id": node_id,
+        });
+        let res: BasicResponse = self
+            .client
+            .cmd("close_connection", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.error {
+            Some(e) => Err(anyhow!("{:#?}", e)),
+            None => Ok(()),
+        }
+    }
+
+    pub async fn get_healthz(&self) -> Result<bool, Error> {
+        let res: HealthzResponse = self.client.cmd("healthz", None).await?.json().await?;
+        Ok(res.success)
+    }
+}
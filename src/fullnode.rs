@@ -1,4 +1,10 @@
-use crate::prelude::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Utc};
+
+use crate::{prelude::*, util::humanize_bytes};
+
+const BLOCK_RECORDS_BATCH_SIZE: u64 = 1000;
 
 #[derive(Clone)]
 pub struct Rpc {
@@ -16,6 +22,17 @@ impl Rpc {
             .json()
             .await?)
     }
+    /// Detects which network the node runs on from its `get_network_info` name.
+    pub async fn get_network(&self) -> Result<Network> {
+        let res = self.get_network_info().await?;
+        Network::from_name(&res.network_name).ok_or_else(|| {
+            anyhow!(
+                "unknown network {} with address prefix {}",
+                res.network_name,
+                res.network_prefix
+            )
+        })
+    }
     pub async fn get_blockchain_state(&self) -> Result<BlockchainState> {
         let res: BlockchainStateResponse = self
             .client
@@ -52,7 +69,7 @@ impl Rpc {
             None => Err(anyhow!("{:#?}", res.error)),
         }
     }
-    pub async fn get_all_mempool_tx_ids(&self) -> Result<Vec<String>> {
+    pub async fn get_all_mempool_tx_ids(&self) -> Result<Vec<Bytes32>> {
         let res: MemPoolTxIdsRespose = self
             .client
             .cmd("get_all_mempool_tx_ids", None)
@@ -65,7 +82,7 @@ impl Rpc {
         }
     }
 
-    pub async fn get_all_mempool_items(&self) -> Result<HashMap<String, MemPoolItem>> {
+    pub async fn get_all_mempool_items(&self) -> Result<HashMap<Bytes32, MemPoolItem>> {
         let res: MemPoolItemsResponse = self
             .client
             .cmd("get_all_mempool_items", None)
@@ -84,7 +101,7 @@ impl Rpc {
             None => Err(anyhow!("{:#?}", res.error)),
         }
     }
-    pub async fn get_block(&self, header_hash: &str) -> Result<Block> {
+    pub async fn get_block(&self, header_hash: &Bytes32) -> Result<Block> {
         let json = json!({
         "header_hash": header_hash,
         });
@@ -183,7 +200,7 @@ impl Rpc {
             None => Err(anyhow!("{:#?}", res.error)),
         }
     }
-    pub async fn get_block_record(&self, header_hash: &str) -> Result<BlockRecord> {
+    pub async fn get_block_record(&self, header_hash: &Bytes32) -> Result<BlockRecord> {
         let json = json!({
         "header_hash": header_hash,
         });
@@ -233,8 +250,8 @@ impl Rpc {
     }
     pub async fn get_network_space(
         &self,
-        older_block_header_hash: &str,
-        newer_block_header_hash: &str,
+        older_block_header_hash: &Bytes32,
+        newer_block_header_hash: &Bytes32,
     ) -> Result<u128> {
         let json = json!(
         {
@@ -253,7 +270,132 @@ impl Rpc {
         }
     }
 
-    pub async fn get_additions(&self, header_hash: &str) -> Result<Vec<CoinRecord>> {
+    /// Estimates the netspace between two heights, like `get_network_space` does for two
+    /// header hashes.
+    pub async fn get_network_space_by_heights(
+        &self,
+        older_height: u64,
+        newer_height: u64,
+    ) -> Result<u128> {
+        let older = self.get_block_record_by_height(older_height).await?;
+        let newer = self.get_block_record_by_height(newer_height).await?;
+        self.get_network_space(&older.header_hash, &newer.header_hash)
+            .await
+    }
+
+    /// Finds the height of the first transaction block with a timestamp at or after `timestamp`,
+    /// or `None` if `timestamp` is after every transaction block up to the peak.
+    pub async fn get_height_by_timestamp(&self, timestamp: DateTime<Utc>) -> Result<Option<u64>> {
+        let peak = self.get_blockchain_state().await?.peak.height;
+        height_by_timestamp(peak, timestamp, |height| {
+            self.get_block_record_by_height(height)
+        })
+        .await
+    }
+
+    /// Computes netspace, block times, transaction block ratio and difficulty changes over
+    /// `window`. Block records are fetched in batches, so wide windows issue several requests.
+    pub async fn get_chain_metrics(&self, window: BlockWindow) -> Result<ChainMetrics> {
+        let (start, end) = match window {
+            BlockWindow::Heights { start, end } => (start, end),
+            BlockWindow::Timestamps { start, end } => {
+                let start = self
+                    .get_height_by_timestamp(start)
+                    .await?
+                    .ok_or_else(|| anyhow!("No transaction block at or after {}", start))?;
+                // A window that ends after the last transaction block runs up to the peak.
+                let end = match self.get_height_by_timestamp(end).await? {
+                    Some(end) => end,
+                    None => self.get_blockchain_state().await?.peak.height,
+                };
+                (start, end)
+            },
+        };
+        if start >= end {
+            return Err(anyhow!(
+                "Invalid block window: start height {} is not below end height {}",
+                start,
+                end
+            ));
+        }
+
+        // One extra record below the window is needed to derive the starting difficulty.
+        let first = start.saturating_sub(1);
+        let mut records = Vec::new();
+        let mut batch_start = first;
+        while batch_start <= end {
+            let batch_end = (batch_start + BLOCK_RECORDS_BATCH_SIZE).min(end + 1);
+            records.extend(self.get_block_records(batch_start, batch_end).await?);
+            batch_start = batch_end;
+        }
+        records.sort_by_key(|r| r.height);
+
+        let mut difficulty_trend: Vec<DifficultyPoint> = Vec::new();
+        for pair in records.windows(2) {
+            // Weight only decreases between records fetched from different forks.
+            let difficulty = pair[1].weight.checked_sub(pair[0].weight).ok_or_else(|| {
+                anyhow!(
+                    "Block weight drops at height {}; the chain reorganized during the query",
+                    pair[1].height
+                )
+            })?;
+            if difficulty_trend.last().map(|p| p.difficulty) != Some(difficulty) {
+                difficulty_trend.push(DifficultyPoint {
+                    height: pair[1].height,
+                    difficulty,
+                });
+            }
+        }
+
+        let window_records: Vec<&BlockRecord> =
+            records.iter().filter(|r| r.height >= start).collect();
+        let transaction_blocks: Vec<(u64, DateTime<Utc>)> = window_records
+            .iter()
+            .filter_map(|r| r.timestamp.map(|ts| (r.height, ts)))
+            .collect();
+        let block_count = window_records.len() as u64;
+        let transaction_block_count = transaction_blocks.len() as u64;
+        let (average_block_time, average_transaction_block_time) =
+            match (transaction_blocks.first(), transaction_blocks.last()) {
+                (Some(first), Some(last)) if first.0 < last.0 => {
+                    let elapsed = (last.1 - first.1).num_milliseconds() as f64 / 1000.0;
+                    (
+                        Some(elapsed / (last.0 - first.0) as f64),
+                        Some(elapsed / (transaction_block_count - 1) as f64),
+                    )
+                },
+                _ => (None, None),
+            };
+
+        let start_header_hash = window_records
+            .first()
+            .map(|r| r.header_hash)
+            .ok_or_else(|| anyhow!("No block records found for heights {}..{}", start, end))?;
+        let end_header_hash = window_records
+            .last()
+            .map(|r| r.header_hash)
+            .unwrap_or_default();
+        let netspace = self
+            .get_network_space(&start_header_hash, &end_header_hash)
+            .await?;
+
+        Ok(ChainMetrics {
+            start_height: start,
+            end_height: end,
+            start_header_hash,
+            end_header_hash,
+            netspace,
+            netspace_human: humanize_bytes(netspace),
+            block_count,
+            transaction_block_count,
+            transaction_block_ratio: transaction_block_count as f64 / block_count as f64,
+            average_block_time,
+            average_transaction_block_time,
+            difficulty_trend,
+        })
+    }
+
+    pub async fn get_additions(&self, header_hash: &Bytes32) -> Result<Vec<CoinRecord>> {
         let json = json!({
         "header_hash": header_hash,
         });
@@ -268,7 +410,7 @@ impl Rpc {
             Some(e) => Err(anyhow!("{:#?}", e)),
         }
     }
-    pub async fn get_removals(&self, header_hash: &str) -> Result<Vec<CoinRecord>> {
+    pub async fn get_removals(&self, header_hash: &Bytes32) -> Result<Vec<CoinRecord>> {
         let json = json!({
         "header_hash": header_hash,
         });
@@ -284,7 +426,7 @@ impl Rpc {
         }
     }
 
-    pub async fn get_state_transitions(&self, header_hash: &str) -> Result<StateTransitions> {
+    pub async fn get_state_transitions(&self, header_hash: &Bytes32) -> Result<StateTransitions> {
         let json = json!({
         "header_hash": header_hash,
         });
@@ -305,7 +447,10 @@ impl Rpc {
             Some(e) => Err(anyhow!("{:#?}", e)),
         }
     }
-    pub async fn get_additions_and_removals(&self, header_hash: &str) -> Result<StateTransitions> {
+    pub async fn get_additions_and_removals(
+        &self,
+        header_hash: &Bytes32,
+    ) -> Result<StateTransitions> {
         let json = json!({
         "header_hash": header_hash,
         });
@@ -326,7 +471,7 @@ impl Rpc {
             Some(e) => Err(anyhow!("{:#?}", e)),
         }
     }
-    pub async fn get_coin_record_by_name(&self, name: &str) -> Result<CoinRecord> {
+    pub async fn get_coin_record_by_name(&self, name: &CoinId) -> Result<CoinRecord> {
         let json = json!({
         "name": name,
         });
@@ -343,7 +488,7 @@ impl Rpc {
     }
     pub async fn get_coin_records_by_names(
         &self,
-        names: Vec<&str>,
+        names: &[CoinId],
         start_height: u64,
         end_height: u64,
         include_spent_coins: bool,
@@ -367,7 +512,7 @@ impl Rpc {
     }
     pub async fn get_coin_records_by_parent_ids(
         &self,
-        parent_ids: Vec<&str>,
+        parent_ids: &[CoinId],
         start_height: u64,
         end_height: u64,
         include_spent_coins: bool,
@@ -394,7 +539,7 @@ impl Rpc {
         start_height: u64,
         end_height: u64,
         include_spent_coins: bool,
-        hint: &str,
+        hint: &Bytes32,
     ) -> Result<Vec<CoinRecord>> {
         let json = json!({
         "hint": hint,
@@ -415,7 +560,7 @@ impl Rpc {
     }
     pub async fn get_puzzle_and_solution(
         &self,
-        coin_id: &str,
+        coin_id: &CoinId,
         height: u64,
     ) -> Result<CoinSolution> {
         let json = json!({
@@ -435,8 +580,8 @@ impl Rpc {
     }
     pub async fn get_recent_signage_point_or_eos(
         &self,
-        sp_hash: Option<&str>,
-        challenge_hash: Option<&str>,
+        sp_hash: Option<&Bytes32>,
+        challenge_hash: Option<&Bytes32>,
     ) -> Result<SignagePointOrEos> {
         let json = if let Some(x) = sp_hash {
             json!({
@@ -458,7 +603,7 @@ impl Rpc {
     }
     pub async fn get_coin_records_by_puzzle_hash(
         &self,
-        puzzle_hash: &str,
+        puzzle_hash: &PuzzleHash,
         start_height: Option<u64>,
         end_height: Option<u64>,
         include_spent_coins: Option<bool>,
@@ -488,7 +633,7 @@ impl Rpc {
     }
     pub async fn get_coin_records_by_puzzle_hashes(
         &self,
-        puzzle_hashes: Vec<&str>,
+        puzzle_hashes: &[PuzzleHash],
         start_height: u64,
         end_height: u64,
         include_spent_coins: bool,
@@ -510,7 +655,7 @@ impl Rpc {
             None => Err(anyhow!("{:#?}", res.error)),
         }
     }
-    pub async fn get_mempool_item_by_tx_id(&self, tx_id: &str) -> Result<MemPoolItem> {
+    pub async fn get_mempool_item_by_tx_id(&self, tx_id: &Bytes32) -> Result<MemPoolItem> {
         let json = json!({
         "tx_id": tx_id,
         });
@@ -526,8 +671,183 @@ impl Rpc {
         }
     }
 
+    pub async fn get_connections(&self) -> Result<Vec<Connection>> {
+        let res: ConnectionsResponse = self
+            .client
+            .cmd("get_connections", None)
+            .await?
+            .json()
+            .await?;
+        match res.connections {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn open_connection(&self, host: &str, port: u16) -> Result<()> {
+        let json = json!({
+            "host": host,
+            "port": port,
+        });
+        let res: BasicResponse = self
+            .client
+            .cmd("open_connection", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.error {
+            Some(e) => Err(anyhow!("{:#?}", e)),
+            None => Ok(()),
+        }
+    }
+
+    pub async fn close_connection(&self, node_id: &str) -> Result<()> {
+        let json = json!({
+            "node_id": node_id,
+        });
+        let res: BasicResponse = self
+            .client
+            .cmd("close_connection", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.error {
+            Some(e) => Err(anyhow!("{:#?}", e)),
+            None => Ok(()),
+        }
+    }
+
+    /// Closes full node peers that fall below the height or throughput thresholds in `policy`
+    /// and then opens connections to every peer listed in `policy.add_peers`.
+    pub async fn curate_peers(&self, policy: &PeerCurationPolicy) -> Result<PeerCurationReport> {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs_f64();
+        let mut report = PeerCurationReport::default();
+        for connection in self.get_connections().await? {
+            if connection.node_type != NodeType::FullNode {
+                continue;
+            }
+            let below_height = match (policy.min_peak_height, connection.peak_height) {
+                (Some(min), Some(height)) => height < min,
+                (Some(_), None) => true,
+                _ => false,
+            };
+            let below_throughput = policy
+                .min_throughput
+                .is_some_and(|min| connection.throughput(now) < min);
+            if below_height || below_throughput {
+                match self.close_connection(&connection.node_id).await {
+                    Ok(()) => report.closed.push(connection),
+                    Err(e) => report.failed.push(format!("{}: {}", connection.node_id, e)),
+                }
+            }
+        }
+        for (host, port) in &policy.add_peers {
+            let peer = format!("{}:{}", host, port);
+            match self.open_connection(host, *port).await {
+                Ok(()) => report.opened.push(peer),
+                Err(e) => report.failed.push(format!("{}: {}", peer, e)),
+            }
+        }
+        Ok(report)
+    }
+
     pub async fn get_healthz(&self) -> Result<bool, Error> {
         let res: HealthzResponse = self.client.cmd("healthz", None).await?.json().await?;
         Ok(res.success)
     }
 }
+
+/// Binary searches heights `0..=peak` for the first one whose latest transaction block is at or
+/// after `timestamp`. Timestamps only grow from one transaction block to the next, and a block
+/// without a timestamp takes that of the transaction block before it, so the first such height
+/// is always a transaction block itself.
+async fn height_by_timestamp<F, Fut>(
+    peak: u64,
+    timestamp: DateTime<Utc>,
+    mut block_record: F,
+) -> Result<Option<u64>>
+where
+    F: FnMut(u64) -> Fut,
+    Fut: std::future::Future<Output = Result<BlockRecord>>,
+{
+    let (mut low, mut high) = (0, peak + 1);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        let record = block_record(mid).await?;
+        let block_timestamp = match record.timestamp {
+            Some(ts) => ts,
+            None => block_record(record.prev_transaction_block_height)
+                .await?
+                .timestamp
+                .ok_or_else(|| anyhow!("No timestamp found for height {}", mid))?,
+        };
+        if block_timestamp < timestamp {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    Ok((low <= peak).then_some(low))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    /// A chain of ten blocks where heights 0, 2, 3, 6 and 9 are transaction blocks.
+    fn chain() -> Vec<BlockRecord> {
+        let mut prev_transaction_block_height = 0;
+        (0..10)
+            .map(|height| {
+                let timestamp = match height {
+                    0 => Some(100),
+                    2 => Some(120),
+                    3 => Some(130),
+                    6 => Some(160),
+                    9 => Some(190),
+                    _ => None,
+                };
+                let record = BlockRecord {
+                    height,
+                    prev_transaction_block_height,
+                    timestamp: timestamp.map(|ts| Utc.timestamp_opt(ts, 0).unwrap()),
+                    ..Default::default()
+                };
+                if timestamp.is_some() {
+                    prev_transaction_block_height = height;
+                }
+                record
+            })
+            .collect()
+    }
+
+    async fn search(chain: &[BlockRecord], peak: u64, timestamp: i64) -> Option<u64> {
+        height_by_timestamp(peak, Utc.timestamp_opt(timestamp, 0).unwrap(), |height| {
+            std::future::ready(Ok(chain[height as usize].clone()))
+        })
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn height_by_timestamp_finds_transaction_blocks() {
+        let chain = chain();
+        for (timestamp, height) in [
+            (50, Some(0)),
+            (100, Some(0)),
+            (101, Some(2)),
+            (125, Some(3)),
+            (131, Some(6)),
+            (160, Some(6)),
+            (190, Some(9)),
+            (191, None),
+        ] {
+            assert_eq!(search(&chain, 9, timestamp).await, height, "{}", timestamp);
+        }
+        // The peak is not a transaction block, and no block after 160 is in the chain yet.
+        assert_eq!(search(&chain, 8, 161).await, None);
+        assert_eq!(search(&chain, 0, 100).await, Some(0));
+    }
+}
@@ -2,11 +2,20 @@ mod error;
 use std::net::{IpAddr, Ipv4Addr};
 
 pub use error::Error;
+pub mod bls;
+pub mod clvm;
+pub mod coin_selection;
 pub mod datalayer;
+pub mod farmer;
 pub mod fullnode;
 pub mod harvester;
 pub mod models;
+pub mod network;
+pub mod offer;
 pub mod prelude;
+pub mod puzzles;
+pub mod streamable;
+pub mod types;
 pub mod util;
 pub mod wallet;
 
@@ -16,6 +25,7 @@ pub struct Config {
     pub addr: SocketAddr,
     pub key_path: PathBuf,
     pub cert_path: PathBuf,
+    pub network: Network,
 }
 
 impl Config {
@@ -24,6 +34,7 @@ impl Config {
             addr,
             key_path: key_path.to_path_buf(),
             cert_path: cert_path.to_path_buf(),
+            network: Network::default(),
         }
     }
 }
@@ -46,6 +57,7 @@ impl ClientBuilder {
                     .into(),
                 cert_path: Path::new("~/.chia/mainnet/config/ssl/full_node/private_full_node.crt")
                     .into(),
+                network: Network::default(),
             },
         }
     }
@@ -68,6 +80,12 @@ impl ClientBuilder {
         self
     }
 
+    /// The network addresses are validated against. Defaults to mainnet.
+    pub fn network(mut self, network: Network) -> Self {
+        self.config.network = network;
+        self
+    }
+
     pub async fn build(self) -> Result<Client> {
         let config = self.config;
         Client::new(&config).await
@@ -78,6 +96,7 @@ impl ClientBuilder {
 pub struct Client {
     pub addr: SocketAddr,
     pub http: reqwest::Client,
+    pub network: Network,
 }
 
 impl Client {
@@ -90,6 +109,7 @@ impl Client {
         Ok(Self {
             addr: config.addr,
             http,
+            network: config.network,
         })
     }
 
@@ -20,8 +20,89 @@ pub struct Connection {
     pub peer_port: i64,
     pub peer_server_port: i64,
     #[serde(rename = "type")]
-    pub type_field: i64,
+    pub node_type: NodeType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_height: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peak_weight: Option<u128>,
+}
+
+impl Connection {
+    /// Average bytes per second exchanged with the peer since the connection was opened.
+    pub fn throughput(&self, now: f64) -> f64 {
+        let elapsed = now - self.creation_time;
+        if elapsed <= 0.0 {
+            return 0.0;
+        }
+        (self.bytes_read + self.bytes_written) as f64 / elapsed
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum NodeType {
+    FullNode,
+    Harvester,
+    Farmer,
+    Timelord,
+    Introducer,
+    Wallet,
+    DataLayer,
+    Unknown(u8),
+}
+
+impl Default for NodeType {
+    fn default() -> Self {
+        NodeType::Unknown(0)
+    }
+}
+
+impl From<u8> for NodeType {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => NodeType::FullNode,
+            2 => NodeType::Harvester,
+            3 => NodeType::Farmer,
+            4 => NodeType::Timelord,
+            5 => NodeType::Introducer,
+            6 => NodeType::Wallet,
+            7 => NodeType::DataLayer,
+            v => NodeType::Unknown(v),
+        }
+    }
+}
+
+impl From<NodeType> for u8 {
+    fn from(value: NodeType) -> Self {
+        match value {
+            NodeType::FullNode => 1,
+            NodeType::Harvester => 2,
+            NodeType::Farmer => 3,
+            NodeType::Timelord => 4,
+            NodeType::Introducer => 5,
+            NodeType::Wallet => 6,
+            NodeType::DataLayer => 7,
+            NodeType::Unknown(v) => v,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerCurationPolicy {
+    pub min_peak_height: Option<u64>,
+    pub min_throughput: Option<f64>,
+    pub add_peers: Vec<(String, u16)>,
 }
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeerCurationReport {
+    pub closed: Vec<Connection>,
+    pub opened: Vec<String>,
+    pub failed: Vec<String>,
+}
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct RoutesResponse {
     pub routes: Option<Vec<String>>,
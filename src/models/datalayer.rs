@@ -1,9 +1,12 @@
 use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use serde_with::{serde_as, NoneAsEmptyString};
 
 use super::wallet::Transaction;
-use crate::util::{deserialize_empty_vec_to_none, deserialize_optional_timestamp};
+use crate::{
+    types::{Bytes32, CoinId, Mojos, PuzzleHash},
+    util::{deserialize_empty_vec_to_none, deserialize_optional_timestamp},
+};
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct BasicResponse {
@@ -15,14 +18,14 @@ pub struct BasicResponse {
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateResponse {
     pub success: bool,
-    pub tx_id: Option<String>,
+    pub tx_id: Option<Bytes32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct VerifyOfferResponse {
-    pub fee: i64,
+    pub fee: Mojos,
     pub success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
@@ -33,18 +36,18 @@ pub struct VerifyOfferResponse {
 pub struct Offer {
     pub maker: Vec<Maker>,
     pub taker: Vec<Taker>,
-    pub fee: i64,
+    pub fee: Mojos,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Maker {
-    pub store_id: String,
+    pub store_id: Bytes32,
     pub inclusions: Vec<Inclusion>,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Taker {
-    pub store_id: String,
+    pub store_id: Bytes32,
     pub inclusions: Vec<Inclusion>,
 }
 
@@ -55,7 +58,7 @@ pub struct Inclusion {
 }
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct ChangeListEntry {
-    pub id: String,
+    pub id: Bytes32,
     pub changelist: Vec<Changelist>,
 }
 
@@ -92,14 +95,14 @@ pub struct Key {
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct KeysValue {
     pub atom: Option<String>,
-    pub hash: String,
+    pub hash: Bytes32,
     pub key: String,
     pub value: String,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct CreateDataStoreResponse {
-    pub id: Option<String>,
+    pub id: Option<Bytes32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
     pub success: bool,
@@ -116,7 +119,7 @@ pub struct KeyValueDiffResponse {
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct LocalRootResponse {
-    pub hash: Option<String>,
+    pub hash: Option<Bytes32>,
     pub success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
@@ -126,7 +129,7 @@ pub struct LocalRootResponse {
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct TakeOfferResponse {
     #[serde_as(as = "NoneAsEmptyString")]
-    pub trade_id: Option<String>,
+    pub trade_id: Option<Bytes32>,
     pub success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
@@ -135,7 +138,7 @@ pub struct TakeOfferResponse {
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct StoresResponse {
     #[serde(deserialize_with = "deserialize_empty_vec_to_none")]
-    pub store_ids: Option<Vec<String>>,
+    pub store_ids: Option<Vec<Bytes32>>,
     pub success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
@@ -152,7 +155,7 @@ pub struct Diff {
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RootResponse {
     pub confirmed: bool,
-    pub hash: Option<String>,
+    pub hash: Option<Bytes32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
     pub success: bool,
@@ -171,8 +174,8 @@ pub struct RootsResponse {
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RootHash {
     pub confirmed: bool,
-    pub hash: String,
-    pub id: String,
+    pub hash: Bytes32,
+    pub id: Bytes32,
     #[serde(deserialize_with = "deserialize_optional_timestamp")]
     pub timestamp: Option<DateTime<Utc>>,
 }
@@ -188,7 +191,7 @@ pub struct RootHistoryResponse {
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RootHistory {
     pub confirmed: bool,
-    pub root_hash: String,
+    pub root_hash: Bytes32,
     #[serde(deserialize_with = "deserialize_optional_timestamp")]
     pub timestamp: Option<DateTime<Utc>>,
 }
@@ -211,9 +214,9 @@ pub struct MirrorsResponse {
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Mirror {
-    pub amount: i64,
-    pub coin_id: String,
-    pub launcher_id: String,
+    pub amount: Mojos,
+    pub coin_id: CoinId,
+    pub launcher_id: Bytes32,
     pub ours: bool,
     pub urls: Vec<String>,
 }
@@ -236,9 +239,9 @@ pub struct SyncStatusResponse {
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SyncStatus {
     pub generation: i64,
-    pub root_hash: String,
+    pub root_hash: Bytes32,
     pub target_generation: i64,
-    pub target_root_hash: String,
+    pub target_root_hash: Bytes32,
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -249,17 +252,134 @@ pub struct OfferResponse {
     pub success: bool,
 }
 
+/// A proof that a key and value, both hex, are in a store, as DataLayer offers carry them.
+/// [`Proof::verify`] checks it against a root hash.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Proof {
     pub key: String,
     pub layers: Vec<Layer>,
-    pub node_hash: String,
+    pub node_hash: Bytes32,
     pub value: String,
 }
 
+/// One step from a Merkle node to its parent: the sibling hash and the parent hash it makes.
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Layer {
-    pub combined_hash: String,
-    pub other_hash: String,
-    pub other_hash_side: String,
+    pub combined_hash: Bytes32,
+    pub other_hash: Bytes32,
+    pub other_hash_side: Side,
+}
+
+/// Which side of the parent node a sibling hash is on. Offers name the side, while
+/// `get_proof` numbers it; both forms are accepted.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Side {
+    #[default]
+    Left,
+    Right,
+}
+
+impl<'de> Deserialize<'de> for Side {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum RawSide {
+            Name(String),
+            Index(u8),
+        }
+        match RawSide::deserialize(d)? {
+            RawSide::Index(0) => Ok(Side::Left),
+            RawSide::Index(1) => Ok(Side::Right),
+            RawSide::Name(name) if name.eq_ignore_ascii_case("left") => Ok(Side::Left),
+            RawSide::Name(name) if name.eq_ignore_ascii_case("right") => Ok(Side::Right),
+            RawSide::Index(v) => Err(de::Error::custom(format!("invalid side {}", v))),
+            RawSide::Name(v) => Err(de::Error::custom(format!("invalid side {}", v))),
+        }
+    }
+}
+
+impl From<Side> for u8 {
+    fn from(value: Side) -> Self {
+        match value {
+            Side::Left => 0,
+            Side::Right => 1,
+        }
+    }
+}
+
+/// Like [`Layer`], with the side numbered as `get_proof` and `verify_proof` expect it.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProofLayer {
+    #[serde(serialize_with = "serialize_side_index")]
+    pub other_hash_side: Side,
+    pub other_hash: Bytes32,
+    pub combined_hash: Bytes32,
+}
+
+fn serialize_side_index<S: Serializer>(side: &Side, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_u8(u8::from(*side))
+}
+
+/// A proof that a key and value are in a store, identified only by their CLVM tree hashes.
+/// [`HashOnlyProof::verify`] checks it against a root hash.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HashOnlyProof {
+    pub key_clvm_hash: Bytes32,
+    pub value_clvm_hash: Bytes32,
+    pub node_hash: Bytes32,
+    pub layers: Vec<ProofLayer>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoreProofs {
+    pub store_id: Bytes32,
+    pub proofs: Vec<HashOnlyProof>,
+}
+
+/// Proofs for keys of a store, with the store singleton coin whose root they lead to.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DlProof {
+    pub coin_id: CoinId,
+    pub inner_puzzle_hash: PuzzleHash,
+    pub store_proofs: StoreProofs,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GetProofResponse {
+    pub success: bool,
+    pub proof: Option<DlProof>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct KeyValueHashes {
+    pub key_clvm_hash: Bytes32,
+    pub value_clvm_hash: Bytes32,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProofResultInclusions {
+    pub store_id: Bytes32,
+    pub inclusions: Vec<KeyValueHashes>,
+}
+
+/// The result of `verify_proof`: the key and value hashes the proof includes, and whether the
+/// root it leads to is still the store's current root.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerifiedProof {
+    pub verified_clvm_hashes: ProofResultInclusions,
+    pub current_root: bool,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VerifyProofResponse {
+    pub success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verified_clvm_hashes: Option<ProofResultInclusions>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_root: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
@@ -2,8 +2,16 @@ use std::collections::HashMap;
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 
-use crate::util::deserialize_optional_timestamp;
+use crate::{
+    clvm::{run_puzzle, Program, SpendConditions},
+    puzzles::PuzzleKind,
+    streamable::Streamable,
+    types::{Bytes32, CoinId, Mojos, PuzzleHash},
+    util::{deserialize_optional_timestamp, int_to_bytes},
+    Error,
+};
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkInfoResponse {
@@ -31,7 +39,7 @@ pub struct BlockchainState {
     pub mempool_max_total_cost: u64,
     pub mempool_min_fees: Option<HashMap<String, u64>>,
     pub mempool_size: u64,
-    pub node_id: String,
+    pub node_id: Bytes32,
     pub peak: Peak,
     pub space: u128,
     pub sub_slot_iters: u64,
@@ -48,24 +56,24 @@ pub struct NetworkSpaceResponse {
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Peak {
-    pub challenge_block_info_hash: String,
+    pub challenge_block_info_hash: Bytes32,
     pub challenge_vdf_output: Output,
     pub deficit: u64,
-    pub farmer_puzzle_hash: String,
-    pub fees: Option<u64>,
-    pub finished_challenge_slot_hashes: Option<Vec<String>>,
-    pub finished_slot_hashes: Option<Vec<String>>,
-    pub finished_reward_slot_hashes: Option<Vec<String>>,
-    pub header_hash: String,
+    pub farmer_puzzle_hash: PuzzleHash,
+    pub fees: Option<Mojos>,
+    pub finished_challenge_slot_hashes: Option<Vec<Bytes32>>,
+    pub finished_slot_hashes: Option<Vec<Bytes32>>,
+    pub finished_reward_slot_hashes: Option<Vec<Bytes32>>,
+    pub header_hash: Bytes32,
     pub height: u64,
     pub overflow: bool,
-    pub pool_puzzle_hash: String,
-    pub prev_hash: String,
-    pub prev_transaction_block_hash: Option<String>,
+    pub pool_puzzle_hash: PuzzleHash,
+    pub prev_hash: Bytes32,
+    pub prev_transaction_block_hash: Option<Bytes32>,
     pub prev_transaction_block_height: u64,
     pub required_iters: u64,
     pub reward_claims_incorporated: Option<Vec<Coin>>,
-    pub reward_infusion_new_challenge: String,
+    pub reward_infusion_new_challenge: Bytes32,
     pub signage_point_index: u64,
     pub sub_epoch_summary_included: Option<String>,
     pub sub_slot_iters: u64,
@@ -101,7 +109,7 @@ pub struct BlockCountMetrics {
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct MemPoolTxIdsRespose {
     pub success: bool,
-    pub tx_ids: Option<Vec<String>>,
+    pub tx_ids: Option<Vec<Bytes32>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
 }
@@ -129,53 +137,59 @@ pub struct BlockHeader {
 pub struct Foliage {
     pub foliage_block_data: FoliageBlockData,
     pub foliage_block_data_signature: String,
-    pub foliage_transaction_block_hash: Option<String>,
+    pub foliage_transaction_block_hash: Option<Bytes32>,
     pub foliage_transaction_block_signature: Option<String>,
-    pub prev_block_hash: String,
-    pub reward_block_hash: String,
+    pub prev_block_hash: Bytes32,
+    pub reward_block_hash: Bytes32,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct FoliageBlockData {
-    pub extension_data: String,
-    pub farmer_reward_puzzle_hash: String,
+    pub extension_data: Bytes32,
+    pub farmer_reward_puzzle_hash: PuzzleHash,
     pub pool_signature: Option<String>,
     pub pool_target: PoolTarget,
-    pub unfinished_reward_block_hash: String,
+    pub unfinished_reward_block_hash: Bytes32,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct PoolTarget {
     pub max_height: u64,
-    pub puzzle_hash: String,
+    pub puzzle_hash: PuzzleHash,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct FoliageTransactionBlock {
-    pub additions_root: String,
-    pub filter_hash: String,
-    pub prev_transaction_block_hash: String,
-    pub removals_root: String,
+    pub additions_root: Bytes32,
+    pub filter_hash: Bytes32,
+    pub prev_transaction_block_hash: Bytes32,
+    pub removals_root: Bytes32,
     #[serde(deserialize_with = "deserialize_optional_timestamp")]
     pub timestamp: Option<DateTime<Utc>>,
-    pub transactions_info_hash: String,
+    pub transactions_info_hash: Bytes32,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct RewardChainBlock {
+    pub challenge_chain_ip_vdf: Option<ChallengeData>,
     pub challenge_chain_sp_vdf: Option<ChallengeData>,
     pub challenge_chain_sp_signature: String,
-    pub pos_ss_cc_challenge_hash: String,
+    pub height: Option<u64>,
+    pub infused_challenge_chain_ip_vdf: Option<ChallengeData>,
+    pub is_transaction_block: Option<bool>,
+    pub pos_ss_cc_challenge_hash: Bytes32,
     pub proof_of_space: ProofOfSpace,
+    pub reward_chain_ip_vdf: Option<ChallengeData>,
     pub reward_chain_sp_signature: String,
     pub reward_chain_sp_vdf: Option<ChallengeData>,
     pub signage_point_index: u64,
     pub total_iters: u64,
+    pub weight: Option<u128>,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct ChallengeData {
-    pub challenge: String,
+    pub challenge: Bytes32,
     pub number_of_iterations: u64,
     pub output: Output,
 }
@@ -187,9 +201,9 @@ pub struct Output {
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct ProofOfSpace {
-    pub challenge: String,
+    pub challenge: Bytes32,
     pub plot_public_key: String,
-    pub pool_contract_puzzle_hash: Option<String>,
+    pub pool_contract_puzzle_hash: Option<PuzzleHash>,
     pub pool_public_key: Option<String>,
     pub proof: String,
     pub size: u64,
@@ -197,7 +211,7 @@ pub struct ProofOfSpace {
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct MemPoolItemsResponse {
-    pub mempool_items: Option<HashMap<String, MemPoolItem>>,
+    pub mempool_items: Option<HashMap<Bytes32, MemPoolItem>>,
     pub success: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<String>,
@@ -214,12 +228,12 @@ pub struct MemPoolItemResponse {
 pub struct MemPoolItem {
     pub additions: Vec<Coin>,
     pub cost: u64,
-    pub fee: u64,
+    pub fee: Mojos,
     pub npc_result: NpcResult,
     pub program: Option<String>,
     pub removals: Option<Vec<Coin>>,
     pub spend_bundle: SpendBundle,
-    pub spend_bundle_name: String,
+    pub spend_bundle_name: Bytes32,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
@@ -240,9 +254,9 @@ pub struct NpcResult {
 */
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct NpcList {
-    pub coin_name: String,
+    pub coin_name: CoinId,
     pub conditions: Vec<(String, Vec<Condition>)>,
-    pub puzzle_hash: String,
+    pub puzzle_hash: PuzzleHash,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
@@ -263,10 +277,10 @@ pub struct Conds {
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Spend {
     pub agg_sig_me: Vec<Vec<String>>,
-    pub coin_id: String,
-    pub create_coin: Vec<(String, u64, Option<String>)>,
+    pub coin_id: CoinId,
+    pub create_coin: Vec<(PuzzleHash, Mojos, Option<String>)>,
     pub height_relative: Option<u64>,
-    pub puzzle_hash: String,
+    pub puzzle_hash: PuzzleHash,
     pub seconds_relative: Option<u64>,
 }
 
@@ -284,6 +298,26 @@ pub struct SpendBundle {
     pub coin_spends: Vec<CoinSpend>,
 }
 
+impl SpendBundle {
+    /// The spend bundle ID: the sha256 of its streamable serialization.
+    pub fn name(&self) -> Result<Bytes32, Error> {
+        self.hash()
+    }
+
+    /// Runs every coin spend, with `max_cost` as the budget for the bundle as a whole.
+    pub fn conditions(&self, max_cost: u64) -> Result<Vec<SpendConditions>, Error> {
+        let mut remaining = max_cost;
+        self.coin_spends
+            .iter()
+            .map(|coin_spend| {
+                let conditions = coin_spend.conditions(remaining)?;
+                remaining -= conditions.clvm_cost;
+                Ok(conditions)
+            })
+            .collect()
+    }
+}
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct CoinSpend {
     pub coin: Coin,
@@ -291,11 +325,49 @@ pub struct CoinSpend {
     pub solution: String,
 }
 
-#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+impl CoinSpend {
+    pub fn puzzle_program(&self) -> Result<Program, Error> {
+        Program::from_hex(&self.puzzle_reveal)
+    }
+
+    pub fn solution_program(&self) -> Result<Program, Error> {
+        Program::from_hex(&self.solution)
+    }
+
+    /// Identifies the puzzle being spent, e.g. a CAT or an NFT, from its puzzle reveal.
+    pub fn puzzle_kind(&self) -> Result<PuzzleKind, Error> {
+        Ok(PuzzleKind::identify(&self.puzzle_program()?))
+    }
+
+    /// Runs the puzzle reveal with the solution and returns the conditions it outputs, failing
+    /// if running costs more than `max_cost` (see [`crate::clvm::MAX_BLOCK_COST_CLVM`]).
+    pub fn conditions(&self, max_cost: u64) -> Result<SpendConditions, Error> {
+        run_puzzle(
+            self.coin.coin_id(),
+            &self.puzzle_program()?,
+            &self.solution_program()?,
+            max_cost,
+        )
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Coin {
-    pub amount: u64,
-    pub parent_coin_info: String,
-    pub puzzle_hash: String,
+    pub amount: Mojos,
+    pub parent_coin_info: CoinId,
+    pub puzzle_hash: PuzzleHash,
+}
+
+impl Coin {
+    /// Computes the coin name: `sha256(parent_coin_info + puzzle_hash + amount)`, with the
+    /// amount encoded as a minimal signed big-endian integer like CLVM atoms.
+    pub fn coin_id(&self) -> CoinId {
+        let mut hasher = Sha256::new();
+        hasher.update(self.parent_coin_info.as_bytes());
+        hasher.update(self.puzzle_hash.as_bytes());
+        hasher.update(int_to_bytes(self.amount.0));
+        CoinId::new(hasher.finalize().into())
+    }
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
@@ -320,7 +392,7 @@ pub struct Block {
     pub finished_sub_slots: Vec<FinishedSubSlot>,
     pub foliage: Foliage,
     pub foliage_transaction_block: Option<FoliageTransactionBlock>,
-    pub header_hash: Option<String>,
+    pub header_hash: Option<Bytes32>,
     pub reward_chain_block: RewardChainBlock,
     pub reward_chain_ip_proof: Option<ChallengeChainProof>,
     pub reward_chain_sp_proof: Option<ChallengeChainProof>,
@@ -340,9 +412,9 @@ pub struct ChallengeChainProof {
 pub struct TransactionsInfo {
     pub aggregated_signature: String,
     pub cost: u64,
-    pub fees: u64,
-    pub generator_refs_root: String,
-    pub generator_root: String,
+    pub fees: Mojos,
+    pub generator_refs_root: Bytes32,
+    pub generator_root: Bytes32,
     pub reward_claims_incorporated: Vec<Coin>,
 }
 
@@ -357,18 +429,18 @@ pub struct FinishedSubSlot {
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct ChallengeChain {
     pub challenge_chain_end_of_slot_vdf: ChallengeData,
-    pub infused_challenge_chain_sub_slot_hash: Option<String>,
+    pub infused_challenge_chain_sub_slot_hash: Option<Bytes32>,
     pub new_difficulty: Option<u64>,
     pub new_sub_slot_iters: Option<u64>,
-    pub subepoch_summary_hash: Option<String>,
+    pub subepoch_summary_hash: Option<Bytes32>,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct RewardChain {
-    pub challenge_chain_sub_slot_hash: String,
+    pub challenge_chain_sub_slot_hash: Bytes32,
     pub deficit: u64,
     pub end_of_slot_vdf: ChallengeData,
-    pub infused_challenge_chain_sub_slot_hash: Option<String>,
+    pub infused_challenge_chain_sub_slot_hash: Option<Bytes32>,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
@@ -396,25 +468,25 @@ pub struct BlockRecordsResponse {
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct BlockRecord {
-    pub challenge_block_info_hash: String,
+    pub challenge_block_info_hash: Bytes32,
     pub challenge_vdf_output: Output,
     pub deficit: Option<u64>,
-    pub farmer_puzzle_hash: String,
-    pub fees: Option<u64>,
-    pub finished_challenge_slot_hashes: Option<Vec<String>>,
-    pub finished_infused_challenge_slot_hashes: Option<Vec<String>>,
-    pub finished_reward_slot_hashes: Option<Vec<String>>,
-    pub header_hash: String,
+    pub farmer_puzzle_hash: PuzzleHash,
+    pub fees: Option<Mojos>,
+    pub finished_challenge_slot_hashes: Option<Vec<Bytes32>>,
+    pub finished_infused_challenge_slot_hashes: Option<Vec<Bytes32>>,
+    pub finished_reward_slot_hashes: Option<Vec<Bytes32>>,
+    pub header_hash: Bytes32,
     pub height: u64,
     pub infused_challenge_vdf_output: Option<Output>,
     pub overflow: bool,
-    pub pool_puzzle_hash: String,
-    pub prev_hash: String,
-    pub prev_transaction_block_hash: Option<String>,
+    pub pool_puzzle_hash: PuzzleHash,
+    pub prev_hash: Bytes32,
+    pub prev_transaction_block_hash: Option<Bytes32>,
     pub prev_transaction_block_height: u64,
     pub required_iters: u64,
     pub reward_claims_incorporated: Option<Vec<Coin>>,
-    pub reward_infusion_new_challenge: String,
+    pub reward_infusion_new_challenge: Bytes32,
     pub signage_point_index: u64,
     pub sub_epoch_summary_included: Option<SubEpochSummaryIncluded>,
     pub sub_slot_iters: u64,
@@ -429,8 +501,8 @@ pub struct SubEpochSummaryIncluded {
     pub new_difficulty: Option<u64>,
     pub new_sub_slot_iters: Option<u64>,
     pub num_blocks_overflow: u64,
-    pub prev_subepoch_summary_hash: String,
-    pub reward_chain_hash: String,
+    pub prev_subepoch_summary_hash: Bytes32,
+    pub reward_chain_hash: Bytes32,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
@@ -506,3 +578,148 @@ pub struct SignagePoint {
     pub rc_vdf: ChallengeData,
     pub rc_proof: ChallengeChainProof,
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlockWindow {
+    Heights {
+        start: u64,
+        end: u64,
+    },
+    Timestamps {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChainMetrics {
+    pub start_height: u64,
+    pub end_height: u64,
+    pub start_header_hash: Bytes32,
+    pub end_header_hash: Bytes32,
+    pub netspace: u128,
+    pub netspace_human: String,
+    pub block_count: u64,
+    pub transaction_block_count: u64,
+    pub transaction_block_ratio: f64,
+    pub average_block_time: Option<f64>,
+    pub average_transaction_block_time: Option<f64>,
+    pub difficulty_trend: Vec<DifficultyPoint>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DifficultyPoint {
+    pub height: u64,
+    pub difficulty: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use super::*;
+
+    /// The mainnet genesis challenge, from which reward coin parents are derived.
+    const GENESIS_CHALLENGE: [u8; 32] =
+        hex!("ccd5bb71183532bff220ba46c268991a3ff07eb358e8255a65c30a2dce0e5fbb");
+    const PUZZLE_HASH: PuzzleHash = PuzzleHash::new(hex!(
+        "cfbfdeed5c4ca2de3d0bf520b9cb4bb7743a359bd2e6a188d19ce7dffc21d3e7"
+    ));
+
+    /// The parent of the pool (`first_half`) or farmer reward coin at `height`: half of the
+    /// genesis challenge followed by the height as a 16 byte integer.
+    fn reward_parent(first_half: bool, height: u128) -> CoinId {
+        let mut parent = [0; 32];
+        let half = if first_half { 0..16 } else { 16..32 };
+        parent[..16].copy_from_slice(&GENESIS_CHALLENGE[half]);
+        parent[16..].copy_from_slice(&height.to_be_bytes());
+        CoinId::new(parent)
+    }
+
+    fn coin(parent_coin_info: CoinId, amount: u64) -> Coin {
+        Coin {
+            amount: Mojos(amount),
+            parent_coin_info,
+            puzzle_hash: PUZZLE_HASH,
+        }
+    }
+
+    #[test]
+    fn coin_id_of_reward_coins() {
+        let pool = coin(reward_parent(true, 1), 1_750_000_000_000);
+        assert_eq!(
+            pool.coin_id(),
+            CoinId::new(hex!(
+                "9a94bfb1d2c58ebd52df02224245c8e2f77f4624d1b7f26a0500a349b2700ef7"
+            ))
+        );
+        let farmer = coin(reward_parent(false, 1), 250_000_000_000);
+        assert_eq!(
+            farmer.coin_id(),
+            CoinId::new(hex!(
+                "0f1bd6d4d292a07d31d3aa2736819e1b5c6a1c0a1b534be1a6af361fe5924bff"
+            ))
+        );
+    }
+
+    #[test]
+    fn coin_id_encodes_amount_as_signed_integer() {
+        // Amounts whose top bit is set need a leading zero byte to stay positive.
+        let cases = [
+            (
+                0,
+                hex!("3e51106a35a461a062d190d73f93f62e1193fdbf4dd9ebf8af5ee804622c4a42"),
+            ),
+            (
+                0x80,
+                hex!("c63d42efe7d4fc6e7e42ce53af9457eea7d780eac35d4708f15ad291cd17694a"),
+            ),
+            (
+                0xff,
+                hex!("f65622a2baa495d19d0522e052289b7e40d53071951dbf889a9070a49a6d9772"),
+            ),
+            (
+                1 << 63,
+                hex!("263e42f36a7e389820957cce641247ee9e13ff065f394fef93b35ddc819f9b36"),
+            ),
+            (
+                u64::MAX,
+                hex!("42b923c6da5d5d4d779fe875d0a84417845bdae2c08f2ae5ccc8811d746408ad"),
+            ),
+        ];
+        for (amount, coin_id) in cases {
+            let coin = coin(reward_parent(true, 1), amount);
+            assert_eq!(coin.coin_id(), CoinId::new(coin_id), "amount {}", amount);
+        }
+    }
+
+    #[test]
+    fn spend_bundle_name() {
+        let infinity = format!("0xc0{}", "00".repeat(95));
+        let empty = SpendBundle {
+            aggregated_signature: infinity.clone(),
+            coin_spends: Vec::new(),
+        };
+        assert_eq!(
+            empty.name().unwrap(),
+            Bytes32::new(hex!(
+                "f2736461882821526d95158a75d4fa10591c7a0c3a6fd29f12282ce042bbaa1d"
+            ))
+        );
+
+        let spend_bundle = SpendBundle {
+            aggregated_signature: infinity,
+            coin_spends: vec![CoinSpend {
+                coin: coin(reward_parent(true, 1), 1_750_000_000_000),
+                puzzle_reveal: "01".to_string(),
+                solution: "ff80ff8080".to_string(),
+            }],
+        };
+        assert_eq!(
+            spend_bundle.name().unwrap(),
+            Bytes32::new(hex!(
+                "04a11a69362b752df57217cd0fe28c319b0d85c15d7b8fff4f345300a97ba3a4"
+            ))
+        );
+    }
+}
@@ -1,8 +1,14 @@
 use std::collections::HashMap;
 
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use super::fullnode::{Coin, SpendBundle};
+use super::fullnode::{Coin, CoinRecord, SpendBundle};
+use crate::{
+    clvm::Program,
+    types::{Address, AssetId, Bytes32, CoinId, Mojos, PuzzleHash},
+    util::{deserialize_hex, serialize_hex},
+};
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct OfferSummaryResponse {
@@ -13,7 +19,7 @@ pub struct OfferSummaryResponse {
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct OfferSummary {
-    pub fees: u64,
+    pub fees: Mojos,
     pub infos: HashMap<String, OfferInfo>,
     pub offered: HashMap<String, u64>,
     pub requested: HashMap<String, u64>,
@@ -24,6 +30,8 @@ pub struct OfferInfo {
     pub tail: Option<String>,
     #[serde(rename = "type")]
     pub type_field: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launcher_id: Option<Bytes32>,
 }
 
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
@@ -37,7 +45,7 @@ pub struct OfferValidityResponse {
 pub struct TransactionResponse {
     pub success: bool,
     pub transaction: Option<Transaction>,
-    pub transaction_id: String,
+    pub transaction_id: Option<Bytes32>,
     pub error: Option<String>,
     pub traceback: Option<String>,
 }
@@ -45,24 +53,260 @@ pub struct TransactionResponse {
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct Transaction {
     pub additions: Vec<Coin>,
-    pub amount: i64,
+    pub amount: Mojos,
     pub confirmed: bool,
     pub confirmed_at_height: i64,
     pub created_at_time: i64,
-    pub fee_amount: i64,
+    pub fee_amount: Mojos,
     pub memos: HashMap<String, String>,
-    pub name: Option<String>,
+    pub name: Option<Bytes32>,
     pub removals: Vec<Coin>,
     pub sent: i64,
-    pub sent_to: Vec<String>,
+    /// Each peer the transaction was sent to, with its mempool inclusion status (1 for
+    /// success, 2 for pending, 3 for failed) and error.
+    pub sent_to: Vec<(String, u8, Option<String>)>,
     pub spend_bundle: Option<SpendBundle>,
-    pub to_puzzle_hash: String,
-    pub trade_id: Option<String>,
+    pub to_puzzle_hash: PuzzleHash,
+    pub trade_id: Option<Bytes32>,
     #[serde(rename = "type")]
-    pub type_field: i64,
+    pub type_field: TransactionType,
     pub wallet_id: i64,
 }
 
+impl Transaction {
+    /// The error of an unconfirmed transaction that the wallet has given up on, following
+    /// `TransactionRecord.is_valid`: it was sent at least six times, no peer accepted it or
+    /// kept it pending, and no peer rejected it for a reason that resending can fix.
+    pub fn rejection(&self) -> Option<&str> {
+        const MINIMUM_SEND_ATTEMPTS: usize = 6;
+        const SUCCESS: u8 = 1;
+        const PENDING: u8 = 2;
+        const TRANSIENT_ERRORS: [&str; 3] = [
+            "INVALID_FEE_LOW_FEE",
+            "INVALID_FEE_TOO_CLOSE_TO_ZERO",
+            "NO_TRANSACTIONS_WHILE_SYNCING",
+        ];
+        if self.confirmed || self.sent_to.len() < MINIMUM_SEND_ATTEMPTS {
+            return None;
+        }
+        let retryable = self.sent_to.iter().any(|(_, status, error)| {
+            *status == SUCCESS
+                || *status == PENDING
+                || error
+                    .as_deref()
+                    .is_some_and(|error| TRANSIENT_ERRORS.contains(&error))
+        });
+        if retryable {
+            return None;
+        }
+        Some(
+            self.sent_to
+                .iter()
+                .find_map(|(_, _, error)| error.as_deref())
+                .unwrap_or("unknown error"),
+        )
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum TransactionType {
+    #[default]
+    IncomingTx,
+    OutgoingTx,
+    CoinbaseReward,
+    FeeReward,
+    IncomingTrade,
+    OutgoingTrade,
+    /// A clawback payment to us that the sender can still claw back.
+    IncomingClawbackReceive,
+    /// A clawback payment we sent, seen from the sender's side.
+    IncomingClawbackSend,
+    /// Claiming or clawing back a clawback payment.
+    OutgoingClawback,
+    IncomingCrcatPending,
+    Unknown(u8),
+}
+
+impl From<u8> for TransactionType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => TransactionType::IncomingTx,
+            1 => TransactionType::OutgoingTx,
+            2 => TransactionType::CoinbaseReward,
+            3 => TransactionType::FeeReward,
+            4 => TransactionType::IncomingTrade,
+            5 => TransactionType::OutgoingTrade,
+            6 => TransactionType::IncomingClawbackReceive,
+            7 => TransactionType::IncomingClawbackSend,
+            8 => TransactionType::OutgoingClawback,
+            9 => TransactionType::IncomingCrcatPending,
+            v => TransactionType::Unknown(v),
+        }
+    }
+}
+
+impl From<TransactionType> for u8 {
+    fn from(value: TransactionType) -> Self {
+        match value {
+            TransactionType::IncomingTx => 0,
+            TransactionType::OutgoingTx => 1,
+            TransactionType::CoinbaseReward => 2,
+            TransactionType::FeeReward => 3,
+            TransactionType::IncomingTrade => 4,
+            TransactionType::OutgoingTrade => 5,
+            TransactionType::IncomingClawbackReceive => 6,
+            TransactionType::IncomingClawbackSend => 7,
+            TransactionType::OutgoingClawback => 8,
+            TransactionType::IncomingCrcatPending => 9,
+            TransactionType::Unknown(v) => v,
+        }
+    }
+}
+
+/// Coin selection limits shared by the endpoints that spend coins.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CoinSelection {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_coin_amount: Option<Mojos>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_coin_amount: Option<Mojos>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub excluded_coin_ids: Vec<CoinId>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub excluded_coin_amounts: Vec<Mojos>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SendTransactionOptions {
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub memos: Vec<String>,
+    /// Wrappers around the recipient's puzzle, such as a clawback time lock.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub puzzle_decorator: Vec<PuzzleDecorator>,
+    #[serde(flatten)]
+    pub coin_selection: CoinSelection,
+}
+
+/// A wrapper the wallet puts around a payment's puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "decorator", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum PuzzleDecorator {
+    /// Lets the sender claw the payment back until `clawback_timelock` seconds after it is
+    /// confirmed; after that, only the recipient can claim it.
+    Clawback { clawback_timelock: u64 },
+}
+
+/// One payment of a `send_transaction_multi` call.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Addition {
+    pub puzzle_hash: PuzzleHash,
+    pub amount: Mojos,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub memos: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TransactionSortKey {
+    ConfirmedAtHeight,
+    Relevance,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum FilterMode {
+    Include,
+    Exclude,
+}
+
+impl TryFrom<u8> for FilterMode {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(FilterMode::Include),
+            2 => Ok(FilterMode::Exclude),
+            v => Err(format!("invalid filter mode {}", v)),
+        }
+    }
+}
+
+impl From<FilterMode> for u8 {
+    fn from(value: FilterMode) -> Self {
+        match value {
+            FilterMode::Include => 1,
+            FilterMode::Exclude => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionTypeFilter {
+    pub values: Vec<TransactionType>,
+    pub mode: FilterMode,
+}
+
+impl TransactionTypeFilter {
+    pub fn include(values: Vec<TransactionType>) -> Self {
+        Self {
+            values,
+            mode: FilterMode::Include,
+        }
+    }
+
+    pub fn exclude(values: Vec<TransactionType>) -> Self {
+        Self {
+            values,
+            mode: FilterMode::Exclude,
+        }
+    }
+}
+
+/// Paging, sorting and filters for `get_transactions`. `start` and `end` index into the
+/// sorted list, so `end - start` is the page size.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GetTransactionsOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_key: Option<TransactionSortKey>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reverse: Option<bool>,
+    /// Only transactions sent to this address.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub type_filter: Option<TransactionTypeFilter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmed: Option<bool>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionsResponse {
+    pub success: bool,
+    pub transactions: Option<Vec<Transaction>>,
+    pub wallet_id: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionCountResponse {
+    pub success: bool,
+    pub count: Option<u64>,
+    pub wallet_id: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct TransactionIdsResponse {
+    pub success: bool,
+    pub transaction_ids: Option<Vec<Bytes32>>,
+    pub error: Option<String>,
+}
+
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct GetWalletBalanceResponse {
     pub success: bool,
@@ -73,14 +317,1327 @@ pub struct GetWalletBalanceResponse {
 
 #[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct WalletBalance {
-    pub confirmed_wallet_balance: i64,
+    pub confirmed_wallet_balance: Mojos,
     pub fingerprint: i64,
-    pub max_send_amount: i64,
-    pub pending_change: i64,
+    pub max_send_amount: Mojos,
+    pub pending_change: Mojos,
     pub pending_coin_removal_count: i64,
-    pub spendable_balance: i64,
-    pub unconfirmed_wallet_balance: i64,
+    pub spendable_balance: Mojos,
+    pub unconfirmed_wallet_balance: Mojos,
     pub unspent_coin_count: i64,
     pub wallet_id: i64,
-    pub wallet_type: i64,
+    pub wallet_type: WalletType,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum WalletType {
+    #[default]
+    Standard,
+    AtomicSwap,
+    AuthorizedPayee,
+    MultiSig,
+    Custody,
+    Cat,
+    Recoverable,
+    DecentralizedId,
+    Pooling,
+    Nft,
+    DataLayer,
+    DataLayerOffer,
+    Vc,
+    Dao,
+    DaoCat,
+    CrCat,
+    Unknown(u8),
+}
+
+impl From<u8> for WalletType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => WalletType::Standard,
+            2 => WalletType::AtomicSwap,
+            3 => WalletType::AuthorizedPayee,
+            4 => WalletType::MultiSig,
+            5 => WalletType::Custody,
+            6 => WalletType::Cat,
+            7 => WalletType::Recoverable,
+            8 => WalletType::DecentralizedId,
+            9 => WalletType::Pooling,
+            10 => WalletType::Nft,
+            11 => WalletType::DataLayer,
+            12 => WalletType::DataLayerOffer,
+            13 => WalletType::Vc,
+            14 => WalletType::Dao,
+            15 => WalletType::DaoCat,
+            57 => WalletType::CrCat,
+            v => WalletType::Unknown(v),
+        }
+    }
+}
+
+impl From<WalletType> for u8 {
+    fn from(value: WalletType) -> Self {
+        match value {
+            WalletType::Standard => 0,
+            WalletType::AtomicSwap => 2,
+            WalletType::AuthorizedPayee => 3,
+            WalletType::MultiSig => 4,
+            WalletType::Custody => 5,
+            WalletType::Cat => 6,
+            WalletType::Recoverable => 7,
+            WalletType::DecentralizedId => 8,
+            WalletType::Pooling => 9,
+            WalletType::Nft => 10,
+            WalletType::DataLayer => 11,
+            WalletType::DataLayerOffer => 12,
+            WalletType::Vc => 13,
+            WalletType::Dao => 14,
+            WalletType::DaoCat => 15,
+            WalletType::CrCat => 57,
+            WalletType::Unknown(v) => v,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct WalletInfo {
+    pub id: u64,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub type_field: WalletType,
+    /// Wallet specific JSON, e.g. a CAT's TAIL hash, if requested with `include_data`.
+    #[serde(default)]
+    pub data: Value,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct WalletsResponse {
+    pub success: bool,
+    pub wallets: Option<Vec<WalletInfo>>,
+    pub fingerprint: Option<u32>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct FingerprintResponse {
+    pub success: bool,
+    pub fingerprint: Option<u32>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct PublicKeysResponse {
+    pub success: bool,
+    pub public_key_fingerprints: Option<Vec<u32>>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct MnemonicResponse {
+    pub success: bool,
+    pub mnemonic: Option<Vec<String>>,
+    pub error: Option<String>,
+}
+
+#[cfg(feature = "private-keys")]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct PrivateKeyResponse {
+    pub success: bool,
+    pub private_key: Option<PrivateKey>,
+    pub error: Option<String>,
+}
+
+/// A key as returned by `get_private_key`. `Debug` leaves out the secret key and seed.
+#[cfg(feature = "private-keys")]
+#[derive(Default, Clone, Serialize, Deserialize)]
+pub struct PrivateKey {
+    pub fingerprint: u32,
+    pub sk: String,
+    pub pk: String,
+    pub farmer_pk: String,
+    pub pool_pk: String,
+    pub seed: Option<String>,
+}
+
+#[cfg(feature = "private-keys")]
+impl std::fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("PrivateKey")
+            .field("fingerprint", &self.fingerprint)
+            .field("pk", &self.pk)
+            .field("farmer_pk", &self.farmer_pk)
+            .field("pool_pk", &self.pool_pk)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct WalletSyncStatusResponse {
+    pub success: bool,
+    pub synced: Option<bool>,
+    pub syncing: Option<bool>,
+    pub genesis_initialized: Option<bool>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletSyncStatus {
+    pub synced: bool,
+    pub syncing: bool,
+    pub genesis_initialized: bool,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct HeightInfoResponse {
+    pub success: bool,
+    pub height: Option<u32>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct NextAddressResponse {
+    pub success: bool,
+    pub address: Option<Address>,
+    pub wallet_id: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct CatAssetIdResponse {
+    pub success: bool,
+    pub asset_id: Option<AssetId>,
+    pub wallet_id: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct CatNameResponse {
+    pub success: bool,
+    pub name: Option<String>,
+    pub wallet_id: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// The name the wallet knows an asset by, and the wallet holding it if there is one.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatAssetName {
+    pub wallet_id: Option<u64>,
+    pub name: String,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct WalletIdResponse {
+    pub success: bool,
+    pub wallet_id: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct CreateCatWalletResponse {
+    pub success: bool,
+    pub asset_id: Option<AssetId>,
+    pub wallet_id: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CatWallet {
+    pub wallet_id: u64,
+    pub asset_id: AssetId,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct StrayCatsResponse {
+    pub success: bool,
+    pub stray_cats: Option<Vec<StrayCat>>,
+    pub error: Option<String>,
+}
+
+/// A CAT the wallet received but has no wallet for.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StrayCat {
+    pub asset_id: AssetId,
+    pub name: String,
+    pub first_seen_height: u32,
+    pub sender_puzzle_hash: PuzzleHash,
+}
+
+/// An NFT as the wallet reports it.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NftInfo {
+    /// The launcher ID encoded as an `nft1...` address.
+    pub nft_id: String,
+    pub launcher_id: Bytes32,
+    pub nft_coin_id: CoinId,
+    pub nft_coin_confirmation_height: u32,
+    pub owner_did: Option<Bytes32>,
+    /// Royalty in basis points, i.e. `300` is 3%.
+    pub royalty_percentage: Option<u16>,
+    pub royalty_puzzle_hash: Option<PuzzleHash>,
+    pub data_uris: Vec<String>,
+    pub data_hash: String,
+    pub metadata_uris: Vec<String>,
+    pub metadata_hash: String,
+    pub license_uris: Vec<String>,
+    pub license_hash: String,
+    pub edition_total: u64,
+    pub edition_number: u64,
+    pub updater_puzhash: PuzzleHash,
+    pub chain_info: String,
+    pub mint_height: u32,
+    pub supports_did: bool,
+    pub p2_address: PuzzleHash,
+    pub pending_transaction: bool,
+    #[serde(default)]
+    pub minter_did: Option<Bytes32>,
+    pub launcher_puzhash: PuzzleHash,
+    #[serde(default)]
+    pub off_chain_metadata: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct NftListResponse {
+    pub success: bool,
+    pub nft_list: Option<Vec<NftInfo>>,
+    pub wallet_id: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct NftInfoResponse {
+    pub success: bool,
+    pub nft_info: Option<NftInfo>,
+    pub error: Option<String>,
+}
+
+/// The on-chain metadata of an NFT to mint. Hashes are sha256 hex of the referenced files.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NftMetadata {
+    pub uris: Vec<String>,
+    pub hash: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub meta_uris: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub meta_hash: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub license_uris: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub license_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edition_number: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edition_total: Option<u64>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NftMintOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub royalty_address: Option<String>,
+    /// Royalty in basis points, i.e. `300` is 3%.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub royalty_percentage: Option<u16>,
+    /// Where the minted NFT goes, the minting wallet if unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_address: Option<String>,
+    /// The `did:chia:1...` DID to assign the NFT to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub did_id: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NftMintBulkOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub royalty_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub royalty_percentage: Option<u16>,
+    /// One target address per NFT, in the order of the metadata list.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub target_list: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mint_number_start: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mint_total: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub xch_change_target: Option<String>,
+    /// Mint from the DID the NFT wallet is attached to.
+    pub mint_from_did: bool,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct NftMintResponse {
+    pub success: bool,
+    pub nft_id: Option<String>,
+    pub spend_bundle: Option<SpendBundle>,
+    pub wallet_id: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct MintedNft {
+    pub nft_id: String,
+    pub spend_bundle: SpendBundle,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct NftMintBulkResponse {
+    pub success: bool,
+    pub nft_id_list: Option<Vec<String>>,
+    pub spend_bundle: Option<SpendBundle>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct MintedNfts {
+    pub nft_id_list: Vec<String>,
+    pub spend_bundle: SpendBundle,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct SpendBundleResponse {
+    pub success: bool,
+    pub spend_bundle: Option<SpendBundle>,
+    pub wallet_id: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// Which URI list `nft_add_uri` appends to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NftUriKind {
+    #[serde(rename = "u")]
+    Data,
+    #[serde(rename = "mu")]
+    Metadata,
+    #[serde(rename = "lu")]
+    License,
+}
+
+/// An asset that pays royalties when traded, e.g. an NFT by its launcher ID.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoyaltyAsset {
+    pub asset: String,
+    pub royalty_address: String,
+    pub royalty_percentage: u16,
+}
+
+/// What a royalty asset is traded against, e.g. `xch` or a CAT's asset ID.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FungibleAsset {
+    pub asset: String,
+    pub amount: Mojos,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoyaltyPayment {
+    pub asset: String,
+    pub address: String,
+    pub amount: Mojos,
+}
+
+/// The royalties owed, keyed by royalty asset.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct RoyaltiesResponse {
+    pub success: bool,
+    pub error: Option<String>,
+    #[serde(flatten)]
+    pub royalties: HashMap<String, Vec<RoyaltyPayment>>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum TradeStatus {
+    #[default]
+    PendingAccept,
+    PendingConfirm,
+    PendingCancel,
+    Cancelled,
+    Confirmed,
+    Failed,
+}
+
+impl TradeStatus {
+    /// Whether the trade can no longer change state.
+    pub fn is_final(self) -> bool {
+        matches!(
+            self,
+            TradeStatus::Cancelled | TradeStatus::Confirmed | TradeStatus::Failed
+        )
+    }
+}
+
+/// The heights and timestamps an offer is valid between, if it restricts them.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidTimes {
+    pub min_height: Option<u32>,
+    pub max_height: Option<u32>,
+    pub min_time: Option<u64>,
+    pub max_time: Option<u64>,
+}
+
+/// An offer the wallet made or took, with its summary.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct TradeRecord {
+    pub trade_id: Bytes32,
+    pub status: TradeStatus,
+    pub is_my_offer: bool,
+    pub confirmed_at_index: u32,
+    pub accepted_at_time: Option<u64>,
+    pub created_at_time: u64,
+    pub sent: u32,
+    pub sent_to: Vec<(String, u8, Option<String>)>,
+    pub coins_of_interest: Vec<Coin>,
+    pub taken_offer: Option<String>,
+    pub summary: Option<OfferSummary>,
+    /// Amounts locked by the offer while it is pending, keyed by asset.
+    #[serde(default)]
+    pub pending: HashMap<String, u64>,
+    #[serde(default)]
+    pub valid_times: Option<ValidTimes>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct TradeRecordResponse {
+    pub success: bool,
+    pub trade_record: Option<TradeRecord>,
+    /// The offer file, for `create_offer_for_ids` or `get_offer` with `file_contents`.
+    pub offer: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedOffer {
+    pub offer: String,
+    pub trade_record: TradeRecord,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateOfferOptions {
+    /// Puzzle drivers for requested assets the wallet does not know, keyed by asset ID.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub driver_dict: HashMap<String, Value>,
+    /// Build the offer without locking coins or storing the trade.
+    pub validate_only: bool,
+    #[serde(flatten)]
+    pub coin_selection: CoinSelection,
+}
+
+/// Paging and filters for `get_all_offers`.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GetAllOffersOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort_key: Option<TransactionSortKey>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reverse: Option<bool>,
+    pub exclude_my_offers: bool,
+    pub exclude_taken_offers: bool,
+    pub include_completed: bool,
+    /// Also return the offer files, in the same order as the trade records.
+    pub file_contents: bool,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct TradeRecordsResponse {
+    pub success: bool,
+    pub trade_records: Option<Vec<TradeRecord>>,
+    pub offers: Option<Vec<String>>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct OffersCountResponse {
+    pub success: bool,
+    pub total: Option<u64>,
+    pub my_offers_count: Option<u64>,
+    pub taken_offers_count: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OffersCount {
+    pub total: u64,
+    pub my_offers_count: u64,
+    pub taken_offers_count: u64,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateDidOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet_name: Option<String>,
+    /// DIDs that can approve recovering this one.
+    pub backup_dids: Vec<String>,
+    pub num_of_backup_ids_needed: u64,
+    pub metadata: HashMap<String, String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct DidResponse {
+    pub success: bool,
+    pub my_did: Option<String>,
+    pub wallet_id: Option<u64>,
+    pub coin_id: Option<CoinId>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DidWallet {
+    pub wallet_id: u64,
+    /// The `did:chia:1...` ID.
+    pub my_did: String,
+}
+
+/// A DID as found on chain by `did_get_info`.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct DidInfo {
+    pub did_id: String,
+    pub launcher_id: Bytes32,
+    pub latest_coin: CoinId,
+    pub p2_address: String,
+    pub public_key: String,
+    pub recovery_list_hash: Option<Bytes32>,
+    pub num_verification: u64,
+    pub metadata: HashMap<String, String>,
+    pub full_puzzle: Program,
+    /// The solution of the latest spend, as nested lists of hex atoms.
+    pub solution: Value,
+    pub hints: Vec<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct DidInfoResponse {
+    pub success: bool,
+    pub did_id: Option<String>,
+    pub launcher_id: Option<Bytes32>,
+    pub latest_coin: Option<CoinId>,
+    pub p2_address: Option<String>,
+    pub public_key: Option<String>,
+    pub recovery_list_hash: Option<Bytes32>,
+    pub num_verification: Option<u64>,
+    pub metadata: Option<HashMap<String, String>>,
+    pub full_puzzle: Option<Program>,
+    pub solution: Option<Value>,
+    pub hints: Option<Vec<String>>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct DidRecoveryListResponse {
+    pub success: bool,
+    pub recovery_list: Option<Vec<String>>,
+    pub num_required: Option<u64>,
+    pub wallet_id: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DidRecoveryList {
+    pub recovery_list: Vec<String>,
+    pub num_required: u64,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct LatestCoinIdResponse {
+    pub success: bool,
+    pub latest_coin_id: Option<CoinId>,
+    pub error: Option<String>,
+}
+
+/// How a message was turned into the bytes that were signed. The names are the strings the
+/// wallet reports in `signing_mode`.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SigningMode {
+    /// CHIP-0002: the tree hash of `("Chia Signed Message" . message)`.
+    #[default]
+    #[serde(rename = "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG:CHIP-0002_")]
+    Chip0002,
+    /// CHIP-0002 with the message given as hex bytes.
+    #[serde(rename = "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG:CHIP-0002_HEX_INPUT_")]
+    Chip0002HexInput,
+    /// The UTF-8 message itself, signed without the CHIP-0002 prefix.
+    #[serde(rename = "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG:utf8input_")]
+    Utf8Input,
+    /// The hex decoded message itself, signed without the CHIP-0002 prefix.
+    #[serde(rename = "BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_AUG:hexinput_")]
+    HexInput,
+}
+
+impl SigningMode {
+    /// The mode the wallet picks for `is_hex` and `safe_mode` when signing.
+    pub fn for_request(is_hex: bool, safe_mode: bool) -> SigningMode {
+        match (is_hex, safe_mode) {
+            (false, true) => SigningMode::Chip0002,
+            (true, true) => SigningMode::Chip0002HexInput,
+            (false, false) => SigningMode::Utf8Input,
+            (true, false) => SigningMode::HexInput,
+        }
+    }
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct SignMessageResponse {
+    pub success: bool,
+    pub pubkey: Option<String>,
+    pub signature: Option<String>,
+    pub signing_mode: Option<SigningMode>,
+    pub latest_coin_id: Option<CoinId>,
+    pub error: Option<String>,
+}
+
+/// A message signature made by the wallet, which [`SignedMessage::verify`] checks offline.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedMessage {
+    pub pubkey: String,
+    pub signature: String,
+    pub signing_mode: SigningMode,
+    /// For `sign_message_by_id`, the coin of the DID or NFT whose key signed.
+    pub latest_coin_id: Option<CoinId>,
+}
+
+impl SignedMessage {
+    pub fn verify(&self, message: &str) -> Result<bool, crate::Error> {
+        crate::bls::verify_signed_message(&self.pubkey, message, &self.signature, self.signing_mode)
+    }
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct VerifySignatureResponse {
+    pub success: bool,
+    #[serde(rename = "isValid")]
+    pub is_valid: Option<bool>,
+    pub error: Option<String>,
+}
+
+/// Coins the wallet can spend right now, and the pending changes it is waiting on.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct SpendableCoins {
+    pub confirmed_records: Vec<CoinRecord>,
+    pub unconfirmed_removals: Vec<Coin>,
+    pub unconfirmed_additions: Vec<Coin>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct SpendableCoinsResponse {
+    pub success: bool,
+    pub confirmed_records: Option<Vec<CoinRecord>>,
+    pub unconfirmed_removals: Option<Vec<Coin>>,
+    pub unconfirmed_additions: Option<Vec<Coin>>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct SelectCoinsResponse {
+    pub success: bool,
+    pub coins: Option<Vec<Coin>>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum CoinType {
+    #[default]
+    Normal,
+    Clawback,
+    CrCatPending,
+    CrCat,
+    Unknown(u8),
+}
+
+impl From<u8> for CoinType {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => CoinType::Normal,
+            1 => CoinType::Clawback,
+            2 => CoinType::CrCatPending,
+            3 => CoinType::CrCat,
+            v => CoinType::Unknown(v),
+        }
+    }
+}
+
+impl From<CoinType> for u8 {
+    fn from(value: CoinType) -> Self {
+        match value {
+            CoinType::Normal => 0,
+            CoinType::Clawback => 1,
+            CoinType::CrCatPending => 2,
+            CoinType::CrCat => 3,
+            CoinType::Unknown(v) => v,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "u8", into = "u8")]
+pub enum CoinRecordOrder {
+    ConfirmedHeight,
+    SpentHeight,
+}
+
+impl TryFrom<u8> for CoinRecordOrder {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            1 => Ok(CoinRecordOrder::ConfirmedHeight),
+            2 => Ok(CoinRecordOrder::SpentHeight),
+            v => Err(format!("invalid coin record order {}", v)),
+        }
+    }
+}
+
+impl From<CoinRecordOrder> for u8 {
+    fn from(value: CoinRecordOrder) -> Self {
+        match value {
+            CoinRecordOrder::ConfirmedHeight => 1,
+            CoinRecordOrder::SpentHeight => 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HashFilter {
+    pub values: Vec<Bytes32>,
+    pub mode: FilterMode,
+}
+
+impl HashFilter {
+    pub fn include(values: Vec<Bytes32>) -> Self {
+        Self {
+            values,
+            mode: FilterMode::Include,
+        }
+    }
+
+    pub fn exclude(values: Vec<Bytes32>) -> Self {
+        Self {
+            values,
+            mode: FilterMode::Exclude,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AmountFilter {
+    pub values: Vec<Mojos>,
+    pub mode: FilterMode,
+}
+
+impl AmountFilter {
+    pub fn include(values: Vec<Mojos>) -> Self {
+        Self {
+            values,
+            mode: FilterMode::Include,
+        }
+    }
+
+    pub fn exclude(values: Vec<Mojos>) -> Self {
+        Self {
+            values,
+            mode: FilterMode::Exclude,
+        }
+    }
+}
+
+/// An inclusive range of block heights.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HeightRange {
+    pub start: u32,
+    pub stop: u32,
+}
+
+/// An inclusive range of coin amounts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AmountRange {
+    pub start: Mojos,
+    pub stop: Mojos,
+}
+
+/// Paging, sorting and filters for `get_coin_records`. The wallet caps `limit` at 1000.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GetCoinRecordsOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallet_type: Option<WalletType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coin_type: Option<CoinType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coin_id_filter: Option<HashFilter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub puzzle_hash_filter: Option<HashFilter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_coin_id_filter: Option<HashFilter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount_filter: Option<AmountFilter>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount_range: Option<AmountRange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirmed_range: Option<HeightRange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spent_range: Option<HeightRange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<CoinRecordOrder>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reverse: Option<bool>,
+    /// Also count every matching record, which is slower on large wallets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_total_count: Option<bool>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalletIdentifier {
+    pub id: u64,
+    #[serde(rename = "type")]
+    pub wallet_type: WalletType,
+}
+
+/// A coin as the wallet tracks it. Heights are 0 until the coin is confirmed or spent.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct WalletCoinRecord {
+    pub id: CoinId,
+    pub amount: Mojos,
+    pub puzzle_hash: PuzzleHash,
+    pub parent_coin_info: CoinId,
+    #[serde(rename = "type")]
+    pub coin_type: CoinType,
+    pub wallet_identifier: WalletIdentifier,
+    /// Extra data for special coins, such as the clawback details of clawback coins.
+    pub metadata: Option<Value>,
+    pub confirmed_height: u32,
+    pub spent_height: u32,
+    pub coinbase: bool,
+}
+
+impl WalletCoinRecord {
+    /// The terms of a clawback coin, or `None` for other coins.
+    pub fn clawback_metadata(&self) -> Option<ClawbackMetadata> {
+        match (self.coin_type, &self.metadata) {
+            (CoinType::Clawback, Some(metadata)) => serde_json::from_value(metadata.clone()).ok(),
+            _ => None,
+        }
+    }
+
+    pub fn coin(&self) -> Coin {
+        Coin {
+            amount: self.amount,
+            parent_coin_info: self.parent_coin_info,
+            puzzle_hash: self.puzzle_hash,
+        }
+    }
+
+    pub fn is_spent(&self) -> bool {
+        self.spent_height != 0
+    }
+}
+
+/// Who may spend a clawback coin: the sender until `time_lock` seconds after it was created,
+/// and the recipient after that.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClawbackMetadata {
+    pub time_lock: u64,
+    pub sender_puzzle_hash: PuzzleHash,
+    pub recipient_puzzle_hash: PuzzleHash,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct WalletCoinRecordsResponse {
+    pub success: bool,
+    pub coin_records: Option<Vec<WalletCoinRecord>>,
+    /// Only set when `include_total_count` was requested.
+    pub total_count: Option<u32>,
+    pub error: Option<String>,
+}
+
+/// Options for `combine_coins`. Without target coins, the wallet picks the smallest coins
+/// (or the largest, with `largest_first`) up to `number_of_coins`.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CombineCoinsOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number_of_coins: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub largest_first: Option<bool>,
+    /// Coins that must be part of the combination.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub target_coin_ids: Vec<CoinId>,
+    /// Stop adding coins once they add up to this amount.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_coin_amount: Option<Mojos>,
+    /// How many coins the wallet considers when picking.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coin_num_limit: Option<u16>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct SendNotificationResponse {
+    pub success: bool,
+    pub tx: Option<Transaction>,
+    pub error: Option<String>,
+}
+
+/// A message sent to one of our puzzle hashes with `send_notification`.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Notification {
+    /// The ID of the coin that carried the notification.
+    pub id: CoinId,
+    #[serde(serialize_with = "serialize_hex", deserialize_with = "deserialize_hex")]
+    pub message: Vec<u8>,
+    /// What the sender paid to send it.
+    pub amount: Mojos,
+    pub height: u32,
+}
+
+impl Notification {
+    /// The message as text, if it is UTF-8 as the CLI and GUI send it.
+    pub fn message_text(&self) -> Option<&str> {
+        std::str::from_utf8(&self.message).ok()
+    }
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct NotificationsResponse {
+    pub success: bool,
+    pub notifications: Option<Vec<Notification>>,
+    pub error: Option<String>,
+}
+
+/// How a singleton coin's parent was spent, which its next spend must prove.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineageProof {
+    pub parent_name: Option<CoinId>,
+    pub inner_puzzle_hash: Option<PuzzleHash>,
+    pub amount: Option<Mojos>,
+}
+
+/// A Verifiable Credential: a singleton, identified by its launcher ID, that commits to the
+/// hash of a set of proofs and names the DID of the provider that may revoke it.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VerifiedCredential {
+    pub coin: Coin,
+    pub singleton_lineage_proof: LineageProof,
+    pub eml_lineage_proof: LineageProof,
+    pub launcher_id: Bytes32,
+    pub inner_puzzle_hash: PuzzleHash,
+    /// The launcher ID of the provider's DID.
+    pub proof_provider: Bytes32,
+    /// The root of the proofs, or `None` before any are set.
+    pub proof_hash: Option<Bytes32>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VcRecord {
+    pub vc: VerifiedCredential,
+    pub confirmed_at_height: u32,
+}
+
+/// What `vc_spend` changes about a credential. Unset fields keep their current value.
+#[derive(Default, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VcSpendOptions {
+    /// Moves the credential to another inner puzzle hash.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_puzhash: Option<PuzzleHash>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_proof_hash: Option<Bytes32>,
+    /// The inner puzzle hash of the provider's DID, needed when it changes the proofs.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_inner_puzhash: Option<PuzzleHash>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct VcRecordResponse {
+    pub success: bool,
+    pub vc_record: Option<VcRecord>,
+    pub transactions: Option<Vec<Transaction>>,
+    pub error: Option<String>,
+}
+
+/// The wallet's credentials, with the proofs it knows for each proof hash. Proofs the wallet
+/// has not been given with `vc_add_proofs` are `None`.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct VcList {
+    pub vc_records: Vec<VcRecord>,
+    pub proofs: HashMap<String, Option<HashMap<String, String>>>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct VcListResponse {
+    pub success: bool,
+    pub vc_records: Option<Vec<VcRecord>>,
+    pub proofs: Option<HashMap<String, Option<HashMap<String, String>>>>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct VcProofsResponse {
+    pub success: bool,
+    pub proofs: Option<HashMap<String, String>>,
+    pub error: Option<String>,
+}
+
+/// The voting rules of a DAO. For an update proposal, only the rules that change need to be
+/// set.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaoRules {
+    /// Blocks a proposal must stay open before it can close.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proposal_timelock: Option<u64>,
+    /// Blocks without new votes a proposal needs before it can close.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub soft_close_length: Option<u64>,
+    /// Votes a proposal needs to pass, in CAT mojos.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attendance_required: Option<u64>,
+    /// The share of yes votes a proposal needs, in hundredths of a percent.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pass_percentage: Option<u64>,
+    /// Blocks after which a stuck proposal can be closed without running it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub self_destruct_length: Option<u64>,
+    /// Blocks between oracle spends of the treasury.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub oracle_spend_delay: Option<u64>,
+    /// The least a proposal must lock up, in mojos.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proposal_minimum_amount: Option<u64>,
+}
+
+/// One payment out of the treasury. Without `asset_id`, the payment is in XCH.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DaoAddition {
+    pub puzzle_hash: PuzzleHash,
+    pub amount: Mojos,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub asset_id: Option<AssetId>,
+}
+
+/// What a DAO proposal does when it passes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "proposal_type", rename_all = "lowercase")]
+pub enum DaoProposal {
+    /// Pays out of the treasury.
+    Spend { additions: Vec<DaoAddition> },
+    /// Changes the DAO's rules.
+    Update { new_dao_rules: DaoRules },
+    /// Mints new DAO CATs to an address.
+    Mint {
+        amount: Mojos,
+        cat_target_address: String,
+    },
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct DaoTreasuryBalanceResponse {
+    pub success: bool,
+    /// Balances keyed by `xch` or the CAT asset ID.
+    pub balances: Option<HashMap<String, Mojos>>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct DaoTransactionResponse {
+    pub success: bool,
+    /// Set when a proposal was created.
+    pub proposal_id: Option<Bytes32>,
+    pub tx_id: Option<Bytes32>,
+    pub tx: Option<Transaction>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct DaoProposalInfo {
+    pub proposal_id: Bytes32,
+    pub inner_puzzle: Program,
+    pub amount_voted: u64,
+    pub yes_votes: u64,
+    pub current_coin: Coin,
+    pub current_innerpuz: Option<Program>,
+    pub timer_coin: Option<Coin>,
+    pub singleton_block_height: u32,
+    pub passed: bool,
+    pub closed: bool,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct DaoProposals {
+    pub proposals: Vec<DaoProposalInfo>,
+    pub proposal_timelock: u64,
+    pub soft_close_length: u64,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct DaoProposalsResponse {
+    pub success: bool,
+    pub proposals: Option<Vec<DaoProposalInfo>>,
+    pub proposal_timelock: Option<u64>,
+    pub soft_close_length: Option<u64>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "u8", into = "u8")]
+pub enum PoolSingletonState {
+    #[default]
+    SelfPooling,
+    /// Waiting out the relative lock height before the plot NFT can move on.
+    LeavingPool,
+    FarmingToPool,
+    Unknown(u8),
+}
+
+impl From<u8> for PoolSingletonState {
+    fn from(value: u8) -> Self {
+        match value {
+            1 => PoolSingletonState::SelfPooling,
+            2 => PoolSingletonState::LeavingPool,
+            3 => PoolSingletonState::FarmingToPool,
+            v => PoolSingletonState::Unknown(v),
+        }
+    }
+}
+
+impl From<PoolSingletonState> for u8 {
+    fn from(value: PoolSingletonState) -> Self {
+        match value {
+            PoolSingletonState::SelfPooling => 1,
+            PoolSingletonState::LeavingPool => 2,
+            PoolSingletonState::FarmingToPool => 3,
+            PoolSingletonState::Unknown(v) => v,
+        }
+    }
+}
+
+/// Where a plot NFT sends its rewards.
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolState {
+    pub version: u8,
+    pub state: PoolSingletonState,
+    /// The pool's puzzle hash, or the owner's when self pooling.
+    pub target_puzzle_hash: PuzzleHash,
+    pub owner_pubkey: String,
+    pub pool_url: Option<String>,
+    /// Blocks the plot NFT must wait after leaving the pool before it can farm elsewhere.
+    pub relative_lock_height: u32,
+}
+
+/// A plot NFT as its pool wallet tracks it.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct PoolWalletInfo {
+    pub current: PoolState,
+    /// The state the wallet is moving to, while a pool change is pending.
+    pub target: Option<PoolState>,
+    pub launcher_coin: Coin,
+    pub launcher_id: Bytes32,
+    /// Where plots assigned to this plot NFT send farming rewards.
+    pub p2_singleton_puzzle_hash: PuzzleHash,
+    pub current_inner: Program,
+    pub tip_singleton_coin_id: CoinId,
+    pub singleton_block_height: u32,
+}
+
+/// The first state of a new plot NFT.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum InitialPoolState {
+    /// Farm to the wallet's own puzzle hash.
+    SelfPooling,
+    FarmingToPool {
+        target_puzzle_hash: PuzzleHash,
+        pool_url: String,
+        relative_lock_height: u32,
+    },
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedPoolWallet {
+    pub transaction: Transaction,
+    pub launcher_id: Bytes32,
+    pub p2_singleton_puzzle_hash: PuzzleHash,
+    pub total_fee: Mojos,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct CreatePoolWalletResponse {
+    pub success: bool,
+    pub transaction: Option<Transaction>,
+    pub launcher_id: Option<Bytes32>,
+    pub p2_singleton_puzzle_hash: Option<PuzzleHash>,
+    pub total_fee: Option<Mojos>,
+    pub error: Option<String>,
+}
+
+/// A pool wallet spend, with the separate transaction paying its fee if there is one.
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct PoolWalletTransaction {
+    pub transaction: Transaction,
+    pub fee_transaction: Option<Transaction>,
+    pub total_fee: Option<Mojos>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct PoolWalletTransactionResponse {
+    pub success: bool,
+    pub transaction: Option<Transaction>,
+    pub fee_transaction: Option<Transaction>,
+    pub total_fee: Option<Mojos>,
+    /// Set by `pw_absorb_rewards`.
+    pub state: Option<PoolWalletInfo>,
+    pub error: Option<String>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct PoolWalletStatusResponse {
+    pub success: bool,
+    pub state: Option<PoolWalletInfo>,
+    pub unconfirmed_transactions: Option<Vec<Transaction>>,
+    pub error: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sent(attempts: &[(u8, Option<&str>)]) -> Transaction {
+        Transaction {
+            sent_to: attempts
+                .iter()
+                .enumerate()
+                .map(|(i, (status, error))| {
+                    (format!("peer{}", i), *status, error.map(String::from))
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn transaction_rejection_follows_is_valid() {
+        const FAILED: (u8, Option<&str>) = (3, Some("DOUBLE_SPEND"));
+
+        assert_eq!(sent(&[]).rejection(), None);
+        assert_eq!(sent(&[FAILED; 5]).rejection(), None);
+        assert_eq!(sent(&[FAILED; 6]).rejection(), Some("DOUBLE_SPEND"));
+        assert_eq!(sent(&[(3, None); 6]).rejection(), Some("unknown error"));
+
+        for keep in [
+            (1, None),
+            (2, Some("MEMPOOL_CONFLICT")),
+            (3, Some("INVALID_FEE_LOW_FEE")),
+            (3, Some("INVALID_FEE_TOO_CLOSE_TO_ZERO")),
+            (3, Some("NO_TRANSACTIONS_WHILE_SYNCING")),
+        ] {
+            let mut attempts = [FAILED; 6];
+            attempts[5] = keep;
+            assert_eq!(sent(&attempts).rejection(), None, "{:?}", keep);
+        }
+
+        let mut confirmed = sent(&[FAILED; 6]);
+        confirmed.confirmed = true;
+        assert_eq!(confirmed.rejection(), None);
+    }
 }
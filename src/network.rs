@@ -0,0 +1,172 @@
+//! Consensus and connection parameters of the public Chia networks.
+
+use std::fmt;
+
+use hex_literal::hex;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    types::{Address, Bytes32, PuzzleHash},
+    Error,
+};
+
+/// The RPC ports services listen on unless configured otherwise. They are the same on every
+/// network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DefaultPorts {
+    pub full_node_rpc: u16,
+    pub wallet_rpc: u16,
+    pub farmer_rpc: u16,
+    pub harvester_rpc: u16,
+    pub datalayer_rpc: u16,
+}
+
+pub const DEFAULT_PORTS: DefaultPorts = DefaultPorts {
+    full_node_rpc: 8555,
+    wallet_rpc: 9256,
+    farmer_rpc: 8559,
+    harvester_rpc: 8560,
+    datalayer_rpc: 8562,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Network {
+    /// The name reported by `get_network_info`, e.g. `testnet11`.
+    pub name: &'static str,
+    /// The human readable part of addresses, e.g. `xch`.
+    pub address_prefix: &'static str,
+    pub genesis_challenge: Bytes32,
+    /// The port full nodes talk to each other on.
+    pub full_node_port: u16,
+    pub ports: DefaultPorts,
+}
+
+pub const MAINNET: Network = Network {
+    name: "mainnet",
+    address_prefix: "xch",
+    genesis_challenge: Bytes32::new(hex!(
+        "ccd5bb71183532bff220ba46c268991a3ff07eb358e8255a65c30a2dce0e5fbb"
+    )),
+    full_node_port: 8444,
+    ports: DEFAULT_PORTS,
+};
+
+pub const TESTNET10: Network = Network {
+    name: "testnet10",
+    address_prefix: "txch",
+    genesis_challenge: Bytes32::new(hex!(
+        "ae83525ba8d1dd3f09b277de18ca3e43fc0af20d20c4b3e92ef2a48bd291ccb2"
+    )),
+    full_node_port: 58444,
+    ports: DEFAULT_PORTS,
+};
+
+pub const TESTNET11: Network = Network {
+    name: "testnet11",
+    address_prefix: "txch",
+    genesis_challenge: Bytes32::new(hex!(
+        "37a90eb5185a9c4439a91ddc98bbadce7b4feba060d50116a067de66bf236615"
+    )),
+    full_node_port: 58444,
+    ports: DEFAULT_PORTS,
+};
+
+/// The network started by `chia dev sim`.
+pub const SIMULATOR: Network = Network {
+    name: "simulator0",
+    address_prefix: "txch",
+    genesis_challenge: Bytes32::new(hex!(
+        "eb8c4d20b322be8d9fddbf9412016bdffe9a2901d7edb0e364e94266d0e095f7"
+    )),
+    full_node_port: 38444,
+    ports: DEFAULT_PORTS,
+};
+
+pub const KNOWN_NETWORKS: [Network; 4] = [MAINNET, TESTNET10, TESTNET11, SIMULATOR];
+
+impl Default for Network {
+    fn default() -> Self {
+        MAINNET
+    }
+}
+
+impl Network {
+    /// Looks up a network by the name a node reports in `get_network_info`.
+    pub fn from_name(name: &str) -> Option<Network> {
+        KNOWN_NETWORKS
+            .into_iter()
+            .find(|network| network.name == name)
+    }
+
+    /// The data appended to `AGG_SIG_ME` messages before signing, which is the genesis
+    /// challenge.
+    pub fn agg_sig_me_additional_data(&self) -> Bytes32 {
+        self.genesis_challenge
+    }
+
+    /// The additional data for any of the `AGG_SIG_*` condition opcodes. `AGG_SIG_UNSAFE`
+    /// (49) has none; the `AGG_SIG_PARENT` through `AGG_SIG_PARENT_PUZZLE` family (43 to 48)
+    /// use `sha256(genesis_challenge + opcode)`.
+    pub fn agg_sig_additional_data(&self, opcode: u8) -> Option<Bytes32> {
+        match opcode {
+            43..=48 => {
+                let mut hasher = Sha256::new();
+                hasher.update(self.genesis_challenge.as_bytes());
+                hasher.update([opcode]);
+                Some(Bytes32::new(hasher.finalize().into()))
+            },
+            50 => Some(self.agg_sig_me_additional_data()),
+            _ => None,
+        }
+    }
+
+    pub fn address(&self, puzzle_hash: PuzzleHash) -> Result<Address, Error> {
+        Address::new(puzzle_hash, self.address_prefix)
+    }
+
+    /// Parses an address, rejecting addresses for other networks.
+    pub fn parse_address(&self, address: &str) -> Result<Address, Error> {
+        Address::parse_with_prefix(address, self.address_prefix)
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_name_finds_known_networks() {
+        for network in KNOWN_NETWORKS {
+            assert_eq!(Network::from_name(network.name), Some(network));
+        }
+        assert_eq!(Network::from_name("testnet12"), None);
+        assert_eq!(Network::from_name("Mainnet"), None);
+    }
+
+    #[test]
+    fn parse_address_checks_the_prefix() {
+        let puzzle_hash = PuzzleHash::new([7; 32]);
+        let testnet_address = TESTNET11.address(puzzle_hash).unwrap().to_string();
+        assert!(testnet_address.starts_with("txch1"));
+
+        assert!(matches!(
+            MAINNET.parse_address(&testnet_address),
+            Err(Error::WrongNetwork { expected, found }) if expected == "xch" && found == "txch"
+        ));
+        let address = TESTNET11.parse_address(&testnet_address).unwrap();
+        assert_eq!(address, TESTNET11.address(puzzle_hash).unwrap());
+
+        let mainnet_address = MAINNET.address(puzzle_hash).unwrap().to_string();
+        assert!(MAINNET.parse_address(&mainnet_address).is_ok());
+        assert!(matches!(
+            TESTNET11.parse_address(&mainnet_address),
+            Err(Error::WrongNetwork { .. })
+        ));
+    }
+}
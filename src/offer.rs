@@ -0,0 +1,390 @@
+//! Offline decoding of `offer1...` strings: bech32m, Chia's dictionary compressed zlib, and
+//! the streamable spend bundle inside, summarized the way the wallet's `get_offer_summary`
+//! does.
+
+use bech32::{convert_bits, Variant};
+use miniz_oxide::inflate::{
+    core::{decompress, inflate_flags, DecompressorOxide},
+    TINFLStatus,
+};
+
+use crate::{
+    clvm::{curry_tree_hash, Program, MAX_BLOCK_COST_CLVM},
+    models::{
+        fullnode::SpendBundle,
+        wallet::{OfferInfo, OfferSummary},
+    },
+    puzzles::{
+        PuzzleKind, CAT_PUZZLE, CAT_PUZZLE_HASH, CAT_PUZZLE_V1, NFT_METADATA_UPDATER,
+        NFT_OWNERSHIP_LAYER, NFT_ROYALTY_TRANSFER_PROGRAM, NFT_STATE_LAYER, SETTLEMENT_PAYMENTS,
+        SETTLEMENT_PAYMENTS_HASH, SETTLEMENT_PAYMENTS_V1, SINGLETON_TOP_LAYER, STANDARD_PUZZLE,
+    },
+    streamable::Streamable,
+    types::{Bytes32, CoinId, Mojos, PuzzleHash},
+    Error,
+};
+
+pub const OFFER_PREFIX: &str = "offer";
+
+/// Chia refuses to inflate offers beyond this size, and so do we.
+const MAX_DECOMPRESSED_SIZE: usize = 6 * 1024 * 1024;
+/// zlib only looks back this far, so only the tail of a dictionary matters.
+const WINDOW_SIZE: usize = 32 * 1024;
+
+/// The preset zlib dictionaries offers are compressed with. Version `n` uses the concatenation
+/// of the first `n` entries, and versions past the last entry use all of them, as in Chia's
+/// `zdict_for_version`.
+///
+/// The default holds Chia's `ZDICT` from `puzzle_compression.py`: the standard puzzle and
+/// CAT v1, then the v1 settlement payments, singleton and NFT puzzles, then CAT v2 and the
+/// current settlement payments, then an empty entry.
+#[derive(Debug, Clone)]
+pub struct OfferDictionary {
+    entries: Vec<Vec<u8>>,
+}
+
+impl Default for OfferDictionary {
+    fn default() -> Self {
+        Self::new(vec![
+            [STANDARD_PUZZLE, CAT_PUZZLE_V1].concat(),
+            [
+                SETTLEMENT_PAYMENTS_V1,
+                SINGLETON_TOP_LAYER,
+                NFT_STATE_LAYER,
+                NFT_OWNERSHIP_LAYER,
+                NFT_METADATA_UPDATER,
+                NFT_ROYALTY_TRANSFER_PROGRAM,
+            ]
+            .concat(),
+            [CAT_PUZZLE, SETTLEMENT_PAYMENTS].concat(),
+            // Chia added this entry to purposefully break compatibility with older wallets.
+            Vec::new(),
+        ])
+    }
+}
+
+impl OfferDictionary {
+    pub fn new(entries: Vec<Vec<u8>>) -> Self {
+        Self { entries }
+    }
+
+    /// Appends the dictionary entry for the next compression version.
+    pub fn push(&mut self, entry: Vec<u8>) {
+        self.entries.push(entry);
+    }
+
+    pub fn versions(&self) -> u16 {
+        self.entries.len() as u16
+    }
+
+    fn for_version(&self, version: u16) -> Vec<u8> {
+        let count = (version as usize).min(self.entries.len());
+        self.entries[..count].concat()
+    }
+}
+
+/// An offer decoded into its spend bundle.
+#[derive(Debug, Clone)]
+pub struct DecodedOffer {
+    pub spend_bundle: SpendBundle,
+    /// The compression version, or `None` if the offer was not compressed.
+    pub compression_version: Option<u16>,
+}
+
+impl DecodedOffer {
+    pub fn decode(offer: &str, dictionary: &OfferDictionary) -> Result<DecodedOffer, Error> {
+        let (prefix, data, variant) = bech32::decode(offer.trim())?;
+        if prefix != OFFER_PREFIX || variant != Variant::Bech32m {
+            return Err(Error::OfferError(format!(
+                "expected a bech32m string with the {} prefix",
+                OFFER_PREFIX
+            )));
+        }
+        let bytes = convert_bits(&data, 5, 8, false)?;
+        // Like Chia, fall back to reading the bytes as-is when they do not decompress.
+        match decompress_offer(&bytes, dictionary) {
+            Ok((version, decompressed)) => Ok(DecodedOffer {
+                spend_bundle: SpendBundle::from_bytes(&decompressed)?,
+                compression_version: Some(version),
+            }),
+            Err(error) => match SpendBundle::from_bytes(&bytes) {
+                Ok(spend_bundle) => Ok(DecodedOffer {
+                    spend_bundle,
+                    compression_version: None,
+                }),
+                Err(_) => Err(error),
+            },
+        }
+    }
+
+    /// Computes what the offer gives and asks for, keyed like the wallet does: `xch`, a CAT's
+    /// TAIL hash or an NFT's launcher ID, in hex without `0x`.
+    ///
+    /// Requested payments come from the settlement spends with an all-zero parent. Offered
+    /// amounts are the coins the other spends create at the settlement puzzle (wrapped in the
+    /// CAT layer for CATs); for NFTs, the offered coin is the odd singleton child of the NFT
+    /// spend. Fees are what the spends consume but do not create.
+    pub fn summary(&self) -> Result<OfferSummary, Error> {
+        let overflow = || Error::OfferError("offer amounts overflow a u64".to_string());
+        let mut summary = OfferSummary::default();
+        let mut removed = Mojos::ZERO;
+        let mut added = Mojos::ZERO;
+        for coin_spend in &self.spend_bundle.coin_spends {
+            let kind = coin_spend.puzzle_kind()?;
+            if coin_spend.coin.parent_coin_info == CoinId::default() {
+                let (asset, info) = settlement_asset(&kind)?;
+                for group in coin_spend.solution_program()?.iter() {
+                    for payment in group.rest().into_iter().flat_map(Program::iter) {
+                        let amount = payment
+                            .rest()
+                            .and_then(Program::first)
+                            .and_then(Program::as_u64)
+                            .ok_or_else(|| {
+                                Error::OfferError(format!("invalid requested payment {}", payment))
+                            })?;
+                        let requested = summary.requested.entry(asset.clone()).or_default();
+                        *requested = requested.checked_add(amount).ok_or_else(overflow)?;
+                    }
+                }
+                if let Some(info) = info {
+                    summary.infos.insert(asset, info);
+                }
+                continue;
+            }
+
+            removed = removed
+                .checked_add(coin_spend.coin.amount)
+                .ok_or_else(overflow)?;
+            let conditions = coin_spend.conditions(MAX_BLOCK_COST_CLVM)?;
+            for (puzzle_hash, amount) in conditions.created_coins() {
+                added = added.checked_add(amount).ok_or_else(overflow)?;
+                if let Some((asset, info)) = offered_asset(&kind, &puzzle_hash, amount) {
+                    let offered = summary.offered.entry(asset.clone()).or_default();
+                    *offered = offered.checked_add(amount.0).ok_or_else(overflow)?;
+                    if let Some(info) = info {
+                        summary.infos.insert(asset, info);
+                    }
+                }
+            }
+        }
+        summary.fees = removed
+            .checked_sub(added)
+            .ok_or_else(|| Error::OfferError("offer creates more than it spends".to_string()))?;
+        Ok(summary)
+    }
+}
+
+fn decompress_offer(bytes: &[u8], dictionary: &OfferDictionary) -> Result<(u16, Vec<u8>), Error> {
+    let invalid = |reason: &str| Error::OfferError(reason.to_string());
+    let [v0, v1, cmf, flg, rest @ ..] = bytes else {
+        return Err(invalid("offer is too short"));
+    };
+    let version = u16::from_be_bytes([*v0, *v1]);
+    if cmf & 0x0f != 8 || (u16::from(*cmf) << 8 | u16::from(*flg)) % 31 != 0 {
+        return Err(invalid("offer is not zlib compressed"));
+    }
+    let zdict = dictionary.for_version(version);
+    let deflated = if flg & 0x20 != 0 {
+        let [d0, d1, d2, d3, deflated @ ..] = rest else {
+            return Err(invalid("offer is too short"));
+        };
+        let expected = u32::from_be_bytes([*d0, *d1, *d2, *d3]);
+        if adler32(&zdict) != expected {
+            return Err(Error::OfferError(format!(
+                "dictionary for compression version {} does not match the offer",
+                version
+            )));
+        }
+        deflated
+    } else {
+        rest
+    };
+    Ok((version, inflate_with_dictionary(deflated, &zdict)?))
+}
+
+/// Inflates a raw deflate stream whose back references may reach into `zdict`, by placing
+/// the dictionary in the output buffer ahead of the data.
+fn inflate_with_dictionary(deflated: &[u8], zdict: &[u8]) -> Result<Vec<u8>, Error> {
+    let window = &zdict[zdict.len().saturating_sub(WINDOW_SIZE)..];
+    let mut out = window.to_vec();
+    out.resize(window.len() + deflated.len() * 4 + 1024, 0);
+    let mut decompressor = DecompressorOxide::new();
+    let (mut in_pos, mut out_pos) = (0, window.len());
+    loop {
+        let (status, consumed, produced) = decompress(
+            &mut decompressor,
+            &deflated[in_pos..],
+            &mut out,
+            out_pos,
+            inflate_flags::TINFL_FLAG_USING_NON_WRAPPING_OUTPUT_BUF,
+        );
+        in_pos += consumed;
+        out_pos += produced;
+        match status {
+            TINFLStatus::Done => {
+                out.truncate(out_pos);
+                return Ok(out.split_off(window.len()));
+            },
+            TINFLStatus::HasMoreOutput if out_pos - window.len() < MAX_DECOMPRESSED_SIZE => {
+                out.resize(out.len() * 2, 0);
+            },
+            status => {
+                return Err(Error::OfferError(format!(
+                    "offer decompression failed: {:?}",
+                    status
+                )))
+            },
+        }
+    }
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    const MOD_ADLER: u32 = 65521;
+    let (mut a, mut b) = (1u32, 0u32);
+    for chunk in bytes.chunks(5552) {
+        for byte in chunk {
+            a += u32::from(*byte);
+            b += a;
+        }
+        a %= MOD_ADLER;
+        b %= MOD_ADLER;
+    }
+    (b << 16) | a
+}
+
+/// Names the asset a requested settlement spend pays out.
+fn settlement_asset(kind: &PuzzleKind) -> Result<(String, Option<OfferInfo>), Error> {
+    match kind {
+        PuzzleKind::SettlementPayments => Ok(("xch".to_string(), None)),
+        PuzzleKind::Cat {
+            tail_hash,
+            inner_puzzle,
+        } if **inner_puzzle == PuzzleKind::SettlementPayments => Ok(cat_asset(tail_hash)),
+        PuzzleKind::Nft { launcher_id, .. } => Ok(nft_asset(launcher_id)),
+        kind => Err(Error::OfferError(format!(
+            "unsupported requested payment puzzle {:?}",
+            kind
+        ))),
+    }
+}
+
+/// Names the asset a created coin offers, if the coin goes to the settlement puzzle.
+fn offered_asset(
+    kind: &PuzzleKind,
+    puzzle_hash: &PuzzleHash,
+    amount: Mojos,
+) -> Option<(String, Option<OfferInfo>)> {
+    let puzzle_hash = Bytes32::from(*puzzle_hash);
+    match kind {
+        PuzzleKind::Cat { tail_hash, .. } => {
+            let settlement = curry_tree_hash(&CAT_PUZZLE_HASH, &[
+                Program::from(CAT_PUZZLE_HASH).tree_hash(),
+                Program::from(*tail_hash).tree_hash(),
+                SETTLEMENT_PAYMENTS_HASH,
+            ]);
+            (puzzle_hash == settlement).then(|| cat_asset(tail_hash))
+        },
+        PuzzleKind::Nft { launcher_id, .. } => (amount.0 % 2 == 1).then(|| nft_asset(launcher_id)),
+        _ => (puzzle_hash == SETTLEMENT_PAYMENTS_HASH).then(|| ("xch".to_string(), None)),
+    }
+}
+
+fn cat_asset(tail_hash: &Bytes32) -> (String, Option<OfferInfo>) {
+    let info = OfferInfo {
+        tail: Some(tail_hash.to_string()),
+        type_field: "CAT".to_string(),
+        ..Default::default()
+    };
+    (hex::encode(tail_hash), Some(info))
+}
+
+fn nft_asset(launcher_id: &Bytes32) -> (String, Option<OfferInfo>) {
+    let info = OfferInfo {
+        launcher_id: Some(*launcher_id),
+        type_field: "singleton".to_string(),
+        ..Default::default()
+    };
+    (hex::encode(launcher_id), Some(info))
+}
+
+/// Decodes an `offer1...` string and summarizes it without a wallet.
+pub fn summarize_offer(offer: &str, dictionary: &OfferDictionary) -> Result<OfferSummary, Error> {
+    DecodedOffer::decode(offer, dictionary)?.summary()
+}
+
+#[cfg(test)]
+mod tests {
+    use hex_literal::hex;
+
+    use super::*;
+    use crate::models::fullnode::{Coin, CoinSpend};
+
+    /// An offer of 1,000 mojos for 500 units of the CAT with TAIL `aa...aa`, with a 10 mojo
+    /// fee, compressed by zlib with Chia's version 6 dictionary like the wallet does.
+    const XCH_FOR_CAT: &str = include_str!("../tests/fixtures/offer/xch_for_cat.offer");
+    const TAIL_HASH: &str = "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa";
+
+    #[test]
+    fn decode_compressed_offer() {
+        let offer = DecodedOffer::decode(XCH_FOR_CAT, &OfferDictionary::default()).unwrap();
+        assert_eq!(offer.compression_version, Some(6));
+        assert_eq!(
+            offer.spend_bundle.name().unwrap(),
+            Bytes32::new(hex!(
+                "34e835f15e2982def88d5190229dde5c1cf752ff2de27cf0607a332f13b2e761"
+            ))
+        );
+
+        let summary = offer.summary().unwrap();
+        assert_eq!(summary.fees, Mojos(10));
+        assert_eq!(summary.offered, [("xch".to_string(), 1000)].into());
+        assert_eq!(summary.requested, [(TAIL_HASH.to_string(), 500)].into());
+        assert_eq!(summary.infos[TAIL_HASH].type_field, "CAT");
+    }
+
+    #[test]
+    fn decode_fails_with_the_wrong_dictionary() {
+        let dictionary = OfferDictionary::new(vec![b"not a puzzle".to_vec()]);
+        let error = DecodedOffer::decode(XCH_FOR_CAT, &dictionary).unwrap_err();
+        assert!(error.to_string().contains("does not match"), "{}", error);
+    }
+
+    #[test]
+    fn dictionary_versions_past_the_last_entry_use_every_entry() {
+        let dictionary = OfferDictionary::default();
+        assert_eq!(dictionary.versions(), 4);
+        assert_eq!(dictionary.for_version(0), Vec::<u8>::new());
+        assert_eq!(
+            dictionary.for_version(1),
+            [STANDARD_PUZZLE, CAT_PUZZLE_V1].concat()
+        );
+        assert_eq!(dictionary.for_version(6), dictionary.for_version(3));
+    }
+
+    #[test]
+    fn summary_rejects_overflowing_amounts() {
+        let coin_spend = CoinSpend {
+            coin: Coin {
+                amount: Mojos(u64::MAX),
+                parent_coin_info: CoinId::new([1; 32]),
+                puzzle_hash: PuzzleHash::default(),
+            },
+            // `(q . ())`, which creates no coins.
+            puzzle_reveal: "ff0180".to_string(),
+            solution: "80".to_string(),
+        };
+        let offer = DecodedOffer {
+            spend_bundle: SpendBundle {
+                aggregated_signature: String::new(),
+                coin_spends: vec![coin_spend.clone(), coin_spend],
+            },
+            compression_version: None,
+        };
+        let error = offer.summary().unwrap_err();
+        assert!(
+            matches!(&error, Error::OfferError(message) if message.contains("overflow")),
+            "{}",
+            error
+        );
+    }
+}
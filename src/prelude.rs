@@ -9,7 +9,11 @@ pub use reqwest::{ClientBuilder, Response};
 pub use serde_json::{json, Value};
 
 pub use crate::{
+    clvm::Program,
     models::{common::*, datalayer::*, fullnode::*, harvester::*, wallet::*},
+    network::Network,
+    puzzles::PuzzleKind,
+    types::{Address, AssetId, Bytes32, CoinId, Mojos, PuzzleHash, Unit},
     util::load_pem_pair,
     Client, Error,
 };
@@ -0,0 +1,536 @@
+//! Recognizes well known puzzles by uncurrying them and matching the module's tree hash.
+
+use hex_literal::hex;
+
+use crate::{
+    clvm::Program,
+    types::{Bytes32, PuzzleHash},
+};
+
+/// `p2_delegated_puzzle_or_hidden_puzzle`, the standard transaction puzzle.
+pub const STANDARD_PUZZLE_HASH: Bytes32 = Bytes32::new(hex!(
+    "e9aaa49f45bad5c889b86ee3341550c155cfdd10c3a6757de618d20612fffd52"
+));
+pub const CAT_PUZZLE_HASH: Bytes32 = Bytes32::new(hex!(
+    "37bef360ee858133b69d595a906dc45d01af50379dad515eb9518abb7c1d2a7a"
+));
+pub const SINGLETON_TOP_LAYER_HASH: Bytes32 = Bytes32::new(hex!(
+    "7faa3253bfddd1e0decb0906b2dc6247bbc4cf608f58345d173adb63e8b47c9f"
+));
+pub const SINGLETON_LAUNCHER_HASH: Bytes32 = Bytes32::new(hex!(
+    "eff07522495060c066f66f32acc2a77e3a3e737aca8baea4d1a64ea4cdc13da9"
+));
+pub const NFT_STATE_LAYER_HASH: Bytes32 = Bytes32::new(hex!(
+    "a04d9f57764f54a43e4030befb4d80026e870519aaa66334aef8304f5d0393c2"
+));
+pub const NFT_OWNERSHIP_LAYER_HASH: Bytes32 = Bytes32::new(hex!(
+    "c5abea79afaa001b5427dfa0c8cf42ca6f38f5841b78f9b3c252733eb2de2726"
+));
+pub const NFT_ROYALTY_TRANSFER_PROGRAM_HASH: Bytes32 = Bytes32::new(hex!(
+    "025dee0fb1e9fa110302a7e9bfb6e381ca09618e2778b0184fa5c6b275cfce1f"
+));
+pub const NFT_METADATA_UPDATER_HASH: Bytes32 = Bytes32::new(hex!(
+    "fe8a4b4e27a2e29a4d3fc7ce9d527adbcaccbab6ada3903ccf3ba9a769d2d78b"
+));
+pub const DID_INNER_PUZZLE_HASH: Bytes32 = Bytes32::new(hex!(
+    "33143d2bef64f14036742673afd158126b94284b4530a28c354fac202b0c910e"
+));
+pub const POOL_MEMBER_INNER_PUZZLE_HASH: Bytes32 = Bytes32::new(hex!(
+    "a8490702e333ddd831a3ac9c22d0fa26d2bfeaf2d33608deb22f0e0123eb0494"
+));
+pub const POOL_WAITING_ROOM_INNER_PUZZLE_HASH: Bytes32 = Bytes32::new(hex!(
+    "a317541a765bf8375e1c6e7c13503d0d2cbf56cacad5182befe947e78e2c0307"
+));
+/// The offer settlement puzzle that holds coins while a trade is taken.
+pub const SETTLEMENT_PAYMENTS_HASH: Bytes32 = Bytes32::new(hex!(
+    "cfbfdeed5c4ca2de3d0bf520b9cb4bb7743a359bd2e6a188d19ce7dffc21d3e7"
+));
+/// CAT v1, replaced by CAT v2 (`CAT_PUZZLE_HASH`) after the 2022 CAT1 vulnerability.
+pub const CAT_PUZZLE_V1_HASH: Bytes32 = Bytes32::new(hex!(
+    "72dec062874cd4d3aab892a0906688a1ae412b0109982e1797a170add88bdcdc"
+));
+/// The settlement puzzle used by offers made before CAT v2.
+pub const SETTLEMENT_PAYMENTS_V1_HASH: Bytes32 = Bytes32::new(hex!(
+    "bae24162efbd568f89bc7a340798a6118df0189eb9e3f8697bcea27af99f8f79"
+));
+
+// Serialized modules of the puzzles above that offer compression uses as its dictionary.
+pub const STANDARD_PUZZLE: &[u8] = &hex!(
+    "ff02ffff01ff02ffff03ff0bffff01ff02ffff03ffff09ff05ffff1dff0bffff1effff0bff0bffff02ff06ffff04ff02ffff04ff17ff8080808080808080ffff01ff02ff17ff2f80ffff01ff088080ff0180ffff01ff04ffff04ff04ffff04ff05ffff04ffff02ff06ffff04ff02ffff04ff17ff80808080ff80808080ffff02ff17ff2f808080ff0180ffff04ffff01ff32ff02ffff03ffff07ff0580ffff01ff0bffff0102ffff02ff06ffff04ff02ffff04ff09ff80808080ffff02ff06ffff04ff02ffff04ff0dff8080808080ffff01ff0bffff0101ff058080ff0180ff018080"
+);
+pub const CAT_PUZZLE: &[u8] = &hex!(
+    "ff02ffff01ff02ff5effff04ff02ffff04ffff04ff05ffff04ffff0bff34ff0580ffff04ff0bff80808080ffff04ffff02ff17ff2f80ffff04ff5fffff04ffff02ff2effff04ff02ffff04ff17ff80808080ffff04ffff02ff2affff04ff02ffff04ff82027fffff04ff82057fffff04ff820b7fff808080808080ffff04ff81bfffff04ff82017fffff04ff8202ffffff04ff8205ffffff04ff820bffff80808080808080808080808080ffff04ffff01ffffffff3d46ff02ff333cffff0401ff01ff81cb02ffffff20ff02ffff03ff05ffff01ff02ff32ffff04ff02ffff04ff0dffff04ffff0bff7cffff0bff34ff2480ffff0bff7cffff0bff7cffff0bff34ff2c80ff0980ffff0bff7cff0bffff0bff34ff8080808080ff8080808080ffff010b80ff0180ffff02ffff03ffff22ffff09ffff0dff0580ff2280ffff09ffff0dff0b80ff2280ffff15ff17ffff0181ff8080ffff01ff0bff05ff0bff1780ffff01ff088080ff0180ffff02ffff03ff0bffff01ff02ffff03ffff09ffff02ff2effff04ff02ffff04ff13ff80808080ff820b9f80ffff01ff02ff56ffff04ff02ffff04ffff02ff13ffff04ff5fffff04ff17ffff04ff2fffff04ff81bfffff04ff82017fffff04ff1bff8080808080808080ffff04ff82017fff8080808080ffff01ff088080ff0180ffff01ff02ffff03ff17ffff01ff02ffff03ffff20ff81bf80ffff0182017fffff01ff088080ff0180ffff01ff088080ff018080ff0180ff04ffff04ff05ff2780ffff04ffff10ff0bff5780ff778080ffffff02ffff03ff05ffff01ff02ffff03ffff09ffff02ffff03ffff09ff11ff5880ffff0159ff8080ff0180ffff01818f80ffff01ff02ff26ffff04ff02ffff04ff0dffff04ff0bffff04ffff04ff81b9ff82017980ff808080808080ffff01ff02ff7affff04ff02ffff04ffff02ffff03ffff09ff11ff5880ffff01ff04ff58ffff04ffff02ff76ffff04ff02ffff04ff13ffff04ff29ffff04ffff0bff34ff5b80ffff04ff2bff80808080808080ff398080ffff01ff02ffff03ffff09ff11ff7880ffff01ff02ffff03ffff20ffff02ffff03ffff09ffff0121ffff0dff298080ffff01ff02ffff03ffff09ffff0cff29ff80ff3480ff5c80ffff01ff0101ff8080ff0180ff8080ff018080ffff0109ffff01ff088080ff0180ffff010980ff018080ff0180ffff04ffff02ffff03ffff09ff11ff5880ffff0159ff8080ff0180ffff04ffff02ff26ffff04ff02ffff04ff0dffff04ff0bffff04ff17ff808080808080ff80808080808080ff0180ffff01ff04ff80ffff04ff80ff17808080ff0180ffff02ffff03ff05ffff01ff04ff09ffff02ff56ffff04ff02ffff04ff0dffff04ff0bff808080808080ffff010b80ff0180ff0bff7cffff0bff34ff2880ffff0bff7cffff0bff7cffff0bff34ff2c80ff0580ffff0bff7cffff02ff32ffff04ff02ffff04ff07ffff04ffff0bff34ff3480ff8080808080ffff0bff34ff8080808080ffff02ffff03ffff07ff0580ffff01ff0bffff0102ffff02ff2effff04ff02ffff04ff09ff80808080ffff02ff2effff04ff02ffff04ff0dff8080808080ffff01ff0bffff0101ff058080ff0180ffff04ffff04ff30ffff04ff5fff808080ffff02ff7effff04ff02ffff04ffff04ffff04ff2fff0580ffff04ff5fff82017f8080ffff04ffff02ff26ffff04ff02ffff04ff0bffff04ff05ffff01ff808080808080ffff04ff17ffff04ff81bfffff04ff82017fffff04ffff02ff2affff04ff02ffff04ff8204ffffff04ffff02ff76ffff04ff02ffff04ff09ffff04ff820affffff04ffff0bff34ff2d80ffff04ff15ff80808080808080ffff04ff8216ffff808080808080ffff04ff8205ffffff04ff820bffff808080808080808080808080ff02ff5affff04ff02ffff04ff5fffff04ff3bffff04ffff02ffff03ff17ffff01ff09ff2dffff02ff2affff04ff02ffff04ff27ffff04ffff02ff76ffff04ff02ffff04ff29ffff04ff57ffff04ffff0bff34ff81b980ffff04ff59ff80808080808080ffff04ff81b7ff80808080808080ff8080ff0180ffff04ff17ffff04ff05ffff04ff8202ffffff04ffff04ffff04ff78ffff04ffff0eff5cffff02ff2effff04ff02ffff04ffff04ff2fffff04ff82017fff808080ff8080808080ff808080ffff04ffff04ff20ffff04ffff0bff81bfff5cffff02ff2effff04ff02ffff04ffff04ff15ffff04ffff10ff82017fffff11ff8202dfff2b80ff8202ff80ff808080ff8080808080ff808080ff138080ff80808080808080808080ff018080"
+);
+pub const CAT_PUZZLE_V1: &[u8] = &hex!(
+    "ff02ffff01ff02ff5effff04ff02ffff04ffff04ff05ffff04ffff0bff2cff0580ffff04ff0bff80808080ffff04ffff02ff17ff2f80ffff04ff5fffff04ffff02ff2effff04ff02ffff04ff17ff80808080ffff04ffff0bff82027fff82057fff820b7f80ffff04ff81bfffff04ff82017fffff04ff8202ffffff04ff8205ffffff04ff820bffff80808080808080808080808080ffff04ffff01ffffffff81ca3dff46ff0233ffff3c04ff01ff0181cbffffff02ff02ffff03ff05ffff01ff02ff32ffff04ff02ffff04ff0dffff04ffff0bff22ffff0bff2cff3480ffff0bff22ffff0bff22ffff0bff2cff5c80ff0980ffff0bff22ff0bffff0bff2cff8080808080ff8080808080ffff010b80ff0180ffff02ffff03ff0bffff01ff02ffff03ffff09ffff02ff2effff04ff02ffff04ff13ff80808080ff820b9f80ffff01ff02ff26ffff04ff02ffff04ffff02ff13ffff04ff5fffff04ff17ffff04ff2fffff04ff81bfffff04ff82017fffff04ff1bff8080808080808080ffff04ff82017fff8080808080ffff01ff088080ff0180ffff01ff02ffff03ff17ffff01ff02ffff03ffff20ff81bf80ffff0182017fffff01ff088080ff0180ffff01ff088080ff018080ff0180ffff04ffff04ff05ff2780ffff04ffff10ff0bff5780ff778080ff02ffff03ff05ffff01ff02ffff03ffff09ffff02ffff03ffff09ff11ff7880ffff0159ff8080ff0180ffff01818f80ffff01ff02ff7affff04ff02ffff04ff0dffff04ff0bffff04ffff04ff81b9ff82017980ff808080808080ffff01ff02ff5affff04ff02ffff04ffff02ffff03ffff09ff11ff7880ffff01ff04ff78ffff04ffff02ff36ffff04ff02ffff04ff13ffff04ff29ffff04ffff0bff2cff5b80ffff04ff2bff80808080808080ff398080ffff01ff02ffff03ffff09ff11ff2480ffff01ff04ff24ffff04ffff0bff20ff2980ff398080ffff010980ff018080ff0180ffff04ffff02ffff03ffff09ff11ff7880ffff0159ff8080ff0180ffff04ffff02ff7affff04ff02ffff04ff0dffff04ff0bffff04ff17ff808080808080ff80808080808080ff0180ffff01ff04ff80ffff04ff80ff17808080ff0180ffffff02ffff03ff05ffff01ff04ff09ffff02ff26ffff04ff02ffff04ff0dffff04ff0bff808080808080ffff010b80ff0180ff0bff22ffff0bff2cff5880ffff0bff22ffff0bff22ffff0bff2cff5c80ff0580ffff0bff22ffff02ff32ffff04ff02ffff04ff07ffff04ffff0bff2cff2c80ff8080808080ffff0bff2cff8080808080ffff02ffff03ffff07ff0580ffff01ff0bffff0102ffff02ff2effff04ff02ffff04ff09ff80808080ffff02ff2effff04ff02ffff04ff0dff8080808080ffff01ff0bff2cff058080ff0180ffff04ffff04ff28ffff04ff5fff808080ffff02ff7effff04ff02ffff04ffff04ffff04ff2fff0580ffff04ff5fff82017f8080ffff04ffff02ff7affff04ff02ffff04ff0bffff04ff05ffff01ff808080808080ffff04ff17ffff04ff81bfffff04ff82017fffff04ffff0bff8204ffffff02ff36ffff04ff02ffff04ff09ffff04ff820affffff04ffff0bff2cff2d80ffff04ff15ff80808080808080ff8216ff80ffff04ff8205ffffff04ff820bffff808080808080808080808080ff02ff2affff04ff02ffff04ff5fffff04ff3bffff04ffff02ffff03ff17ffff01ff09ff2dffff0bff27ffff02ff36ffff04ff02ffff04ff29ffff04ff57ffff04ffff0bff2cff81b980ffff04ff59ff80808080808080ff81b78080ff8080ff0180ffff04ff17ffff04ff05ffff04ff8202ffffff04ffff04ffff04ff24ffff04ffff0bff7cff2fff82017f80ff808080ffff04ffff04ff30ffff04ffff0bff81bfffff0bff7cff15ffff10ff82017fffff11ff8202dfff2b80ff8202ff808080ff808080ff138080ff80808080808080808080ff018080"
+);
+pub const SINGLETON_TOP_LAYER: &[u8] = &hex!(
+    "ff02ffff01ff02ffff03ffff18ff2fff3480ffff01ff04ffff04ff20ffff04ff2fff808080ffff04ffff02ff3effff04ff02ffff04ff05ffff04ffff02ff2affff04ff02ffff04ff27ffff04ffff02ffff03ff77ffff01ff02ff36ffff04ff02ffff04ff09ffff04ff57ffff04ffff02ff2effff04ff02ffff04ff05ff80808080ff808080808080ffff011d80ff0180ffff04ffff02ffff03ff77ffff0181b7ffff015780ff0180ff808080808080ffff04ff77ff808080808080ffff02ff3affff04ff02ffff04ff05ffff04ffff02ff0bff5f80ffff01ff8080808080808080ffff01ff088080ff0180ffff04ffff01ffffffff4947ff0233ffff0401ff0102ffffff20ff02ffff03ff05ffff01ff02ff32ffff04ff02ffff04ff0dffff04ffff0bff3cffff0bff34ff2480ffff0bff3cffff0bff3cffff0bff34ff2c80ff0980ffff0bff3cff0bffff0bff34ff8080808080ff8080808080ffff010b80ff0180ffff02ffff03ffff22ffff09ffff0dff0580ff2280ffff09ffff0dff0b80ff2280ffff15ff17ffff0181ff8080ffff01ff0bff05ff0bff1780ffff01ff088080ff0180ff02ffff03ff0bffff01ff02ffff03ffff02ff26ffff04ff02ffff04ff13ff80808080ffff01ff02ffff03ffff20ff1780ffff01ff02ffff03ffff09ff81b3ffff01818f80ffff01ff02ff3affff04ff02ffff04ff05ffff04ff1bffff04ff34ff808080808080ffff01ff04ffff04ff23ffff04ffff02ff36ffff04ff02ffff04ff09ffff04ff53ffff04ffff02ff2effff04ff02ffff04ff05ff80808080ff808080808080ff738080ffff02ff3affff04ff02ffff04ff05ffff04ff1bffff04ff34ff8080808080808080ff0180ffff01ff088080ff0180ffff01ff04ff13ffff02ff3affff04ff02ffff04ff05ffff04ff1bffff04ff17ff8080808080808080ff0180ffff01ff02ffff03ff17ff80ffff01ff088080ff018080ff0180ffffff02ffff03ffff09ff09ff3880ffff01ff02ffff03ffff18ff2dffff010180ffff01ff0101ff8080ff0180ff8080ff0180ff0bff3cffff0bff34ff2880ffff0bff3cffff0bff3cffff0bff34ff2c80ff0580ffff0bff3cffff02ff32ffff04ff02ffff04ff07ffff04ffff0bff34ff3480ff8080808080ffff0bff34ff8080808080ffff02ffff03ffff07ff0580ffff01ff0bffff0102ffff02ff2effff04ff02ffff04ff09ff80808080ffff02ff2effff04ff02ffff04ff0dff8080808080ffff01ff0bffff0101ff058080ff0180ff02ffff03ffff21ff17ffff09ff0bff158080ffff01ff04ff30ffff04ff0bff808080ffff01ff088080ff0180ff018080"
+);
+pub const NFT_STATE_LAYER: &[u8] = &hex!(
+    "ff02ffff01ff02ff3effff04ff02ffff04ff05ffff04ffff02ff2fff5f80ffff04ff80ffff04ffff04ffff04ff0bffff04ff17ff808080ffff01ff808080ffff01ff8080808080808080ffff04ffff01ffffff0233ff04ff0101ffff02ff02ffff03ff05ffff01ff02ff1affff04ff02ffff04ff0dffff04ffff0bff12ffff0bff2cff1480ffff0bff12ffff0bff12ffff0bff2cff3c80ff0980ffff0bff12ff0bffff0bff2cff8080808080ff8080808080ffff010b80ff0180ffff0bff12ffff0bff2cff1080ffff0bff12ffff0bff12ffff0bff2cff3c80ff0580ffff0bff12ffff02ff1affff04ff02ffff04ff07ffff04ffff0bff2cff2c80ff8080808080ffff0bff2cff8080808080ffff02ffff03ffff07ff0580ffff01ff0bffff0102ffff02ff2effff04ff02ffff04ff09ff80808080ffff02ff2effff04ff02ffff04ff0dff8080808080ffff01ff0bffff0101ff058080ff0180ff02ffff03ff0bffff01ff02ffff03ffff09ff23ff1880ffff01ff02ffff03ffff18ff81b3ff2c80ffff01ff02ffff03ffff20ff1780ffff01ff02ff3effff04ff02ffff04ff05ffff04ff1bffff04ff33ffff04ff2fffff04ff5fff8080808080808080ffff01ff088080ff0180ffff01ff04ff13ffff02ff3effff04ff02ffff04ff05ffff04ff1bffff04ff17ffff04ff2fffff04ff5fff80808080808080808080ff0180ffff01ff02ffff03ffff09ff23ffff0181e880ffff01ff02ff3effff04ff02ffff04ff05ffff04ff1bffff04ff17ffff04ffff02ffff03ffff22ffff09ffff02ff2effff04ff02ffff04ff53ff80808080ff82014f80ffff20ff5f8080ffff01ff02ff53ffff04ff818fffff04ff82014fffff04ff81b3ff8080808080ffff01ff088080ff0180ffff04ff2cff8080808080808080ffff01ff04ff13ffff02ff3effff04ff02ffff04ff05ffff04ff1bffff04ff17ffff04ff2fffff04ff5fff80808080808080808080ff018080ff0180ffff01ff04ffff04ff18ffff04ffff02ff16ffff04ff02ffff04ff05ffff04ff27ffff04ffff0bff2cff82014f80ffff04ffff02ff2effff04ff02ffff04ff818fff80808080ffff04ffff0bff2cff0580ff8080808080808080ff378080ff81af8080ff0180ff018080"
+);
+pub const NFT_OWNERSHIP_LAYER: &[u8] = &hex!(
+    "ff02ffff01ff02ff26ffff04ff02ffff04ff05ffff04ff17ffff04ff0bffff04ffff02ff2fff5f80ff80808080808080ffff04ffff01ffffff82ad4cff0233ffff3e04ff81f601ffffff0102ffff02ffff03ff05ffff01ff02ff2affff04ff02ffff04ff0dffff04ffff0bff32ffff0bff3cff3480ffff0bff32ffff0bff32ffff0bff3cff2280ff0980ffff0bff32ff0bffff0bff3cff8080808080ff8080808080ffff010b80ff0180ff04ffff04ff38ffff04ffff02ff36ffff04ff02ffff04ff05ffff04ff27ffff04ffff02ff2effff04ff02ffff04ffff02ffff03ff81afffff0181afffff010b80ff0180ff80808080ffff04ffff0bff3cff4f80ffff04ffff0bff3cff0580ff8080808080808080ff378080ff82016f80ffffff02ff3effff04ff02ffff04ff05ffff04ff0bffff04ff17ffff04ff2fffff04ff2fffff01ff80ff808080808080808080ff0bff32ffff0bff3cff2880ffff0bff32ffff0bff32ffff0bff3cff2280ff0580ffff0bff32ffff02ff2affff04ff02ffff04ff07ffff04ffff0bff3cff3c80ff8080808080ffff0bff3cff8080808080ffff02ffff03ffff07ff0580ffff01ff0bffff0102ffff02ff2effff04ff02ffff04ff09ff80808080ffff02ff2effff04ff02ffff04ff0dff8080808080ffff01ff0bffff0101ff058080ff0180ff02ffff03ff5fffff01ff02ffff03ffff09ff82011fff3880ffff01ff02ffff03ffff09ffff18ff82059f80ff3c80ffff01ff02ffff03ffff20ff81bf80ffff01ff02ff3effff04ff02ffff04ff05ffff04ff0bffff04ff17ffff04ff2fffff04ff81dfffff04ff82019fffff04ff82017fff80808080808080808080ffff01ff088080ff0180ffff01ff04ff819fffff02ff3effff04ff02ffff04ff05ffff04ff0bffff04ff17ffff04ff2fffff04ff81dfffff04ff81bfffff04ff82017fff808080808080808080808080ff0180ffff01ff02ffff03ffff09ff82011fff2c80ffff01ff02ffff03ffff20ff82017f80ffff01ff04ffff04ff24ffff04ffff0eff10ffff02ff2effff04ff02ffff04ff82019fff8080808080ff808080ffff02ff3effff04ff02ffff04ff05ffff04ff0bffff04ff17ffff04ff2fffff04ff81dfffff04ff81bfffff04ffff02ff0bffff04ff17ffff04ff2fffff04ff82019fff8080808080ff8080808080808080808080ffff01ff088080ff0180ffff01ff02ffff03ffff09ff82011fff2480ffff01ff02ffff03ffff20ffff02ffff03ffff09ffff0122ffff0dff82029f8080ffff01ff02ffff03ffff09ffff0cff82029fff80ffff010280ff1080ffff01ff0101ff8080ff0180ff8080ff018080ffff01ff04ff819fffff02ff3effff04ff02ffff04ff05ffff04ff0bffff04ff17ffff04ff2fffff04ff81dfffff04ff81bfffff04ff82017fff8080808080808080808080ffff01ff088080ff0180ffff01ff04ff819fffff02ff3effff04ff02ffff04ff05ffff04ff0bffff04ff17ffff04ff2fffff04ff81dfffff04ff81bfffff04ff82017fff808080808080808080808080ff018080ff018080ff0180ffff01ff02ff3affff04ff02ffff04ff05ffff04ff0bffff04ff81bfffff04ffff02ffff03ff82017fffff0182017fffff01ff02ff0bffff04ff17ffff04ff2fffff01ff808080808080ff0180ff8080808080808080ff0180ff018080"
+);
+pub const NFT_ROYALTY_TRANSFER_PROGRAM: &[u8] = &hex!(
+    "ff02ffff01ff02ffff03ff81bfffff01ff04ff82013fffff04ff80ffff04ffff02ffff03ffff22ff82013fffff20ffff09ff82013fff2f808080ffff01ff04ffff04ff10ffff04ffff0bffff02ff2effff04ff02ffff04ff09ffff04ff8205bfffff04ffff02ff3effff04ff02ffff04ffff04ff09ffff04ff82013fff1d8080ff80808080ff808080808080ff1580ff808080ffff02ff16ffff04ff02ffff04ff0bffff04ff17ffff04ff8202bfffff04ff15ff8080808080808080ffff01ff02ff16ffff04ff02ffff04ff0bffff04ff17ffff04ff8202bfffff04ff15ff8080808080808080ff0180ff80808080ffff01ff04ff2fffff01ff80ff80808080ff0180ffff04ffff01ffffff3f02ff04ff0101ffff822710ff02ff02ffff03ff05ffff01ff02ff3affff04ff02ffff04ff0dffff04ffff0bff2affff0bff2cff1480ffff0bff2affff0bff2affff0bff2cff3c80ff0980ffff0bff2aff0bffff0bff2cff8080808080ff8080808080ffff010b80ff0180ffff02ffff03ff17ffff01ff04ffff04ff10ffff04ffff0bff81a7ffff02ff3effff04ff02ffff04ffff04ff2fffff04ffff04ff05ffff04ffff05ffff14ffff12ff47ff0b80ff128080ffff04ffff04ff05ff8080ff80808080ff808080ff8080808080ff808080ffff02ff16ffff04ff02ffff04ff05ffff04ff0bffff04ff37ffff04ff2fff8080808080808080ff8080ff0180ffff0bff2affff0bff2cff1880ffff0bff2affff0bff2affff0bff2cff3c80ff0580ffff0bff2affff02ff3affff04ff02ffff04ff07ffff04ffff0bff2cff2c80ff8080808080ffff0bff2cff8080808080ff02ffff03ffff07ff0580ffff01ff0bffff0102ffff02ff3effff04ff02ffff04ff09ff80808080ffff02ff3effff04ff02ffff04ff0dff8080808080ffff01ff0bffff0101ff058080ff0180ff018080"
+);
+pub const NFT_METADATA_UPDATER: &[u8] = &hex!(
+    "ff02ffff01ff04ffff04ffff02ffff03ffff22ff27ff3780ffff01ff02ffff03ffff21ffff09ff27ffff01826d7580ffff09ff27ffff01826c7580ffff09ff27ffff01758080ffff01ff02ff02ffff04ff02ffff04ff05ffff04ff27ffff04ff37ff808080808080ffff010580ff0180ffff010580ff0180ffff04ff0bff808080ffff01ff808080ffff04ffff01ff02ffff03ff05ffff01ff02ffff03ffff09ff11ff0b80ffff01ff04ffff04ff0bffff04ff17ff198080ff0d80ffff01ff04ff09ffff02ff02ffff04ff02ffff04ff0dffff04ff0bffff04ff17ff8080808080808080ff0180ff8080ff0180ff018080"
+);
+pub const SETTLEMENT_PAYMENTS: &[u8] = &hex!(
+    "ff02ffff01ff02ff0affff04ff02ffff04ff03ff80808080ffff04ffff01ffff333effff02ffff03ff05ffff01ff04ffff04ff0cffff04ffff02ff1effff04ff02ffff04ff09ff80808080ff808080ffff02ff16ffff04ff02ffff04ff19ffff04ffff02ff0affff04ff02ffff04ff0dff80808080ff808080808080ff8080ff0180ffff02ffff03ff05ffff01ff02ffff03ffff15ff29ff8080ffff01ff04ffff04ff08ff0980ffff02ff16ffff04ff02ffff04ff0dffff04ff0bff808080808080ffff01ff088080ff0180ffff010b80ff0180ff02ffff03ffff07ff0580ffff01ff0bffff0102ffff02ff1effff04ff02ffff04ff09ff80808080ffff02ff1effff04ff02ffff04ff0dff8080808080ffff01ff0bffff0101ff058080ff0180ff018080"
+);
+pub const SETTLEMENT_PAYMENTS_V1: &[u8] = &hex!(
+    "ff02ffff01ff02ff0affff04ff02ffff04ff03ff80808080ffff04ffff01ffff333effff02ffff03ff05ffff01ff04ffff04ff0cffff04ffff02ff1effff04ff02ffff04ff09ff80808080ff808080ffff02ff16ffff04ff02ffff04ff19ffff04ffff02ff0affff04ff02ffff04ff0dff80808080ff808080808080ff8080ff0180ffff02ffff03ff05ffff01ff04ffff04ff08ff0980ffff02ff16ffff04ff02ffff04ff0dffff04ff0bff808080808080ffff010b80ff0180ff02ffff03ffff07ff0580ffff01ff0bffff0102ffff02ff1effff04ff02ffff04ff09ff80808080ffff02ff1effff04ff02ffff04ff0dff8080808080ffff01ff0bffff0101ff058080ff0180ff018080"
+);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PuzzleKind {
+    Standard {
+        synthetic_key: Vec<u8>,
+    },
+    Cat {
+        tail_hash: Bytes32,
+        inner_puzzle: Box<PuzzleKind>,
+    },
+    /// A singleton whose inner puzzle is not one of the singleton kinds below.
+    Singleton {
+        launcher_id: Bytes32,
+        inner_puzzle: Box<PuzzleKind>,
+    },
+    SingletonLauncher,
+    Nft {
+        launcher_id: Bytes32,
+        metadata: Program,
+        metadata_updater_hash: Bytes32,
+        /// The DID the NFT is assigned to, if it has an ownership layer and an owner.
+        owner_did: Option<Bytes32>,
+        royalty_puzzle_hash: Option<PuzzleHash>,
+        /// Royalty in basis points, i.e. `300` is 3%.
+        royalty_percentage: Option<u16>,
+        inner_puzzle: Box<PuzzleKind>,
+    },
+    Did {
+        launcher_id: Bytes32,
+        recovery_list_hash: Option<Bytes32>,
+        num_verifications_required: u64,
+        metadata: Program,
+        inner_puzzle: Box<PuzzleKind>,
+    },
+    Pool {
+        launcher_id: Bytes32,
+        pool_puzzle_hash: PuzzleHash,
+        owner_public_key: Vec<u8>,
+        state: PoolPuzzleState,
+    },
+    /// A DataLayer store: a singleton with an NFT state layer whose metadata is the root hash.
+    DataLayer {
+        launcher_id: Bytes32,
+        root_hash: Option<Bytes32>,
+        inner_puzzle: Box<PuzzleKind>,
+    },
+    SettlementPayments,
+    Unknown {
+        puzzle_hash: PuzzleHash,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PoolPuzzleState {
+    FarmingToPool,
+    LeavingPool { relative_lock_height: u32 },
+}
+
+impl PuzzleKind {
+    /// Classifies a puzzle reveal. Puzzles that are not recognized, or whose curried arguments
+    /// do not have the expected shape, come back as [`PuzzleKind::Unknown`].
+    pub fn identify(puzzle: &Program) -> PuzzleKind {
+        identify_curried(puzzle).unwrap_or_else(|| match puzzle.tree_hash() {
+            hash if hash == SINGLETON_LAUNCHER_HASH => PuzzleKind::SingletonLauncher,
+            hash if hash == SETTLEMENT_PAYMENTS_HASH => PuzzleKind::SettlementPayments,
+            hash => PuzzleKind::Unknown {
+                puzzle_hash: hash.into(),
+            },
+        })
+    }
+
+    pub fn launcher_id(&self) -> Option<Bytes32> {
+        match self {
+            PuzzleKind::Singleton { launcher_id, .. }
+            | PuzzleKind::Nft { launcher_id, .. }
+            | PuzzleKind::Did { launcher_id, .. }
+            | PuzzleKind::Pool { launcher_id, .. }
+            | PuzzleKind::DataLayer { launcher_id, .. } => Some(*launcher_id),
+            _ => None,
+        }
+    }
+}
+
+fn identify_curried(puzzle: &Program) -> Option<PuzzleKind> {
+    let (module, args) = puzzle.uncurry()?;
+    let mod_hash = module.tree_hash();
+    if mod_hash == STANDARD_PUZZLE_HASH {
+        let [synthetic_key] = &args[..] else {
+            return None;
+        };
+        return Some(PuzzleKind::Standard {
+            synthetic_key: synthetic_key.as_atom()?.to_vec(),
+        });
+    }
+    if mod_hash == CAT_PUZZLE_HASH {
+        let [_, tail_hash, inner_puzzle] = &args[..] else {
+            return None;
+        };
+        return Some(PuzzleKind::Cat {
+            tail_hash: bytes32(tail_hash)?,
+            inner_puzzle: Box::new(PuzzleKind::identify(inner_puzzle)),
+        });
+    }
+    if mod_hash == SINGLETON_TOP_LAYER_HASH {
+        let [singleton_struct, inner_puzzle] = &args[..] else {
+            return None;
+        };
+        return identify_singleton(singleton_struct, inner_puzzle);
+    }
+    None
+}
+
+/// Reads `(MOD_HASH . (LAUNCHER_ID . LAUNCHER_PUZZLE_HASH))` and classifies the inner puzzle.
+fn identify_singleton(singleton_struct: &Program, inner_puzzle: &Program) -> Option<PuzzleKind> {
+    let launcher_id = bytes32(singleton_struct.rest()?.first()?)?;
+    let Some((module, args)) = inner_puzzle.uncurry() else {
+        return Some(PuzzleKind::Singleton {
+            launcher_id,
+            inner_puzzle: Box::new(PuzzleKind::identify(inner_puzzle)),
+        });
+    };
+    let mod_hash = module.tree_hash();
+    let kind = if mod_hash == NFT_STATE_LAYER_HASH {
+        let [_, metadata, updater_hash, inner] = &args[..] else {
+            return None;
+        };
+        // DataLayer stores reuse the NFT state layer with the root hash as metadata.
+        if metadata.is_pair() {
+            identify_nft(launcher_id, metadata, updater_hash, inner)?
+        } else {
+            PuzzleKind::DataLayer {
+                launcher_id,
+                root_hash: optional_bytes32(metadata)?,
+                inner_puzzle: Box::new(PuzzleKind::identify(inner)),
+            }
+        }
+    } else if mod_hash == DID_INNER_PUZZLE_HASH {
+        let [inner, recovery_list_hash, num_verifications_required, _, metadata] = &args[..] else {
+            return None;
+        };
+        PuzzleKind::Did {
+            launcher_id,
+            recovery_list_hash: optional_bytes32(recovery_list_hash)?,
+            num_verifications_required: num_verifications_required.as_u64()?,
+            metadata: metadata.clone(),
+            inner_puzzle: Box::new(PuzzleKind::identify(inner)),
+        }
+    } else if mod_hash == POOL_MEMBER_INNER_PUZZLE_HASH
+        || mod_hash == POOL_WAITING_ROOM_INNER_PUZZLE_HASH
+    {
+        let [pool_puzzle_hash, _, owner_public_key, _, last] = &args[..] else {
+            return None;
+        };
+        let state = if mod_hash == POOL_MEMBER_INNER_PUZZLE_HASH {
+            PoolPuzzleState::FarmingToPool
+        } else {
+            PoolPuzzleState::LeavingPool {
+                relative_lock_height: u32::try_from(last.as_u64()?).ok()?,
+            }
+        };
+        PuzzleKind::Pool {
+            launcher_id,
+            pool_puzzle_hash: bytes32(pool_puzzle_hash)?.into(),
+            owner_public_key: owner_public_key.as_atom()?.to_vec(),
+            state,
+        }
+    } else {
+        PuzzleKind::Singleton {
+            launcher_id,
+            inner_puzzle: Box::new(PuzzleKind::identify(inner_puzzle)),
+        }
+    };
+    Some(kind)
+}
+
+fn identify_nft(
+    launcher_id: Bytes32,
+    metadata: &Program,
+    updater_hash: &Program,
+    inner: &Program,
+) -> Option<PuzzleKind> {
+    let mut owner_did = None;
+    let mut royalty_puzzle_hash = None;
+    let mut royalty_percentage = None;
+    let mut p2_puzzle = inner;
+    let ownership_layer = inner
+        .uncurry()
+        .filter(|(module, _)| module.tree_hash() == NFT_OWNERSHIP_LAYER_HASH);
+    if let Some((_, args)) = &ownership_layer {
+        let [_, current_owner, transfer_program, inner] = &args[..] else {
+            return None;
+        };
+        owner_did = optional_bytes32(current_owner)?;
+        p2_puzzle = inner;
+        let royalty = transfer_program
+            .uncurry()
+            .filter(|(module, _)| module.tree_hash() == NFT_ROYALTY_TRANSFER_PROGRAM_HASH);
+        if let Some((_, args)) = royalty {
+            let [_, puzzle_hash, percentage] = &args[..] else {
+                return None;
+            };
+            royalty_puzzle_hash = Some(bytes32(puzzle_hash)?.into());
+            royalty_percentage = Some(u16::try_from(percentage.as_u64()?).ok()?);
+        }
+    }
+    Some(PuzzleKind::Nft {
+        launcher_id,
+        metadata: metadata.clone(),
+        metadata_updater_hash: bytes32(updater_hash)?,
+        owner_did,
+        royalty_puzzle_hash,
+        royalty_percentage,
+        inner_puzzle: Box::new(PuzzleKind::identify(p2_puzzle)),
+    })
+}
+
+fn bytes32(program: &Program) -> Option<Bytes32> {
+    Bytes32::try_from(program.as_atom()?).ok()
+}
+
+/// Reads a 32 byte atom where `()` stands for "none", failing on anything else.
+fn optional_bytes32(program: &Program) -> Option<Option<Bytes32>> {
+    match program.is_nil() {
+        true => Some(None),
+        false => bytes32(program).map(Some),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SINGLETON_LAUNCHER: &[u8] = &hex!(
+        "ff02ffff01ff04ffff04ff04ffff04ff05ffff04ff0bff80808080ffff04ffff04ff0affff04ffff02ff0effff04ff02ffff04ffff04ff05ffff04ff0bffff04ff17ff80808080ff80808080ff808080ff808080ffff04ffff01ff33ff3cff02ffff03ffff07ff0580ffff01ff0bffff0102ffff02ff0effff04ff02ffff04ff09ff80808080ffff02ff0effff04ff02ffff04ff0dff8080808080ffff01ff0bffff0101ff058080ff0180ff018080"
+    );
+    const DID_INNER_PUZZLE: &[u8] = &hex!(
+        "ff02ffff01ff02ffff03ff81bfffff01ff02ff05ff82017f80ffff01ff02ffff03ffff22ffff09ffff02ff7effff04ff02ffff04ff8217ffff80808080ff0b80ffff15ff17ff808080ffff01ff04ffff04ff28ffff04ff82017fff808080ffff04ffff04ff34ffff04ff8202ffffff04ff82017fffff04ffff04ff8202ffff8080ff8080808080ffff04ffff04ff38ffff04ff822fffff808080ffff02ff26ffff04ff02ffff04ff2fffff04ff17ffff04ff8217ffffff04ff822fffffff04ff8202ffffff04ff8205ffffff04ff820bffffff01ff8080808080808080808080808080ffff01ff088080ff018080ff0180ffff04ffff01ffffffff313dff4946ffff0233ff3c04ffffff0101ff02ff02ffff03ff05ffff01ff02ff3affff04ff02ffff04ff0dffff04ffff0bff2affff0bff22ff3c80ffff0bff2affff0bff2affff0bff22ff3280ff0980ffff0bff2aff0bffff0bff22ff8080808080ff8080808080ffff010b80ff0180ffffff02ffff03ff17ffff01ff02ffff03ff82013fffff01ff04ffff04ff30ffff04ffff0bffff0bffff02ff36ffff04ff02ffff04ff05ffff04ff27ffff04ff82023fffff04ff82053fffff04ff820b3fff8080808080808080ffff02ff7effff04ff02ffff04ffff02ff2effff04ff02ffff04ff2fffff04ff5fffff04ff82017fff808080808080ff8080808080ff2f80ff808080ffff02ff26ffff04ff02ffff04ff05ffff04ff0bffff04ff37ffff04ff2fffff04ff5fffff04ff8201bfffff04ff82017fffff04ffff10ff8202ffffff010180ff808080808080808080808080ffff01ff02ff26ffff04ff02ffff04ff05ffff04ff37ffff04ff2fffff04ff5fffff04ff8201bfffff04ff82017fffff04ff8202ffff8080808080808080808080ff0180ffff01ff02ffff03ffff15ff8202ffffff11ff0bffff01018080ffff01ff04ffff04ff20ffff04ff82017fffff04ff5fff80808080ff8080ffff01ff088080ff018080ff0180ff0bff17ffff02ff5effff04ff02ffff04ff09ffff04ff2fffff04ffff02ff7effff04ff02ffff04ffff04ff09ffff04ff0bff1d8080ff80808080ff808080808080ff5f80ffff04ffff0101ffff04ffff04ff2cffff04ff05ff808080ffff04ffff04ff20ffff04ff17ffff04ff0bff80808080ff80808080ffff0bff2affff0bff22ff2480ffff0bff2affff0bff2affff0bff22ff3280ff0580ffff0bff2affff02ff3affff04ff02ffff04ff07ffff04ffff0bff22ff2280ff8080808080ffff0bff22ff8080808080ff02ffff03ffff07ff0580ffff01ff0bffff0102ffff02ff7effff04ff02ffff04ff09ff80808080ffff02ff7effff04ff02ffff04ff0dff8080808080ffff01ff0bffff0101ff058080ff0180ff018080"
+    );
+    const SYNTHETIC_KEY: [u8; 48] = [0xaa; 48];
+    const LAUNCHER_ID: Bytes32 = Bytes32::new([0x11; 32]);
+
+    fn module(bytes: &[u8]) -> Program {
+        Program::from_bytes(bytes).unwrap()
+    }
+
+    fn standard() -> Program {
+        module(STANDARD_PUZZLE).curry(&[Program::atom(SYNTHETIC_KEY)])
+    }
+
+    fn standard_kind() -> Box<PuzzleKind> {
+        Box::new(PuzzleKind::Standard {
+            synthetic_key: SYNTHETIC_KEY.to_vec(),
+        })
+    }
+
+    fn singleton_struct() -> Program {
+        Program::cons(
+            Program::atom(SINGLETON_TOP_LAYER_HASH.as_ref()),
+            Program::cons(
+                Program::atom(LAUNCHER_ID.as_ref()),
+                Program::atom(SINGLETON_LAUNCHER_HASH.as_ref()),
+            ),
+        )
+    }
+
+    fn singleton(inner_puzzle: Program) -> Program {
+        module(SINGLETON_TOP_LAYER).curry(&[singleton_struct(), inner_puzzle])
+    }
+
+    fn nft_state_layer(metadata: Program, inner_puzzle: Program) -> Program {
+        module(NFT_STATE_LAYER).curry(&[
+            Program::atom(NFT_STATE_LAYER_HASH.as_ref()),
+            metadata,
+            Program::atom(NFT_METADATA_UPDATER_HASH.as_ref()),
+            inner_puzzle,
+        ])
+    }
+
+    #[test]
+    fn identify_standard_and_cat() {
+        assert_eq!(PuzzleKind::identify(&standard()), *standard_kind());
+
+        let tail_hash = Bytes32::new([0x22; 32]);
+        let cat = module(CAT_PUZZLE).curry(&[
+            Program::atom(CAT_PUZZLE_HASH.as_ref()),
+            Program::atom(tail_hash.as_ref()),
+            standard(),
+        ]);
+        assert_eq!(PuzzleKind::identify(&cat), PuzzleKind::Cat {
+            tail_hash,
+            inner_puzzle: standard_kind(),
+        });
+    }
+
+    #[test]
+    fn identify_singletons() {
+        assert_eq!(
+            PuzzleKind::identify(&singleton(standard())),
+            PuzzleKind::Singleton {
+                launcher_id: LAUNCHER_ID,
+                inner_puzzle: standard_kind(),
+            }
+        );
+        assert_eq!(
+            PuzzleKind::identify(&module(SINGLETON_LAUNCHER)),
+            PuzzleKind::SingletonLauncher
+        );
+        assert_eq!(
+            PuzzleKind::identify(&module(SETTLEMENT_PAYMENTS)),
+            PuzzleKind::SettlementPayments
+        );
+    }
+
+    #[test]
+    fn identify_nft() {
+        let metadata = Program::list([Program::cons(
+            Program::atom(b"u".to_vec()),
+            Program::list([Program::atom(b"https://example.com/nft.png".to_vec())]),
+        )]);
+        let owner_did = Bytes32::new([0x33; 32]);
+        let royalty_puzzle_hash = PuzzleHash::new([0x44; 32]);
+        let transfer_program = module(NFT_ROYALTY_TRANSFER_PROGRAM).curry(&[
+            singleton_struct(),
+            Program::atom(royalty_puzzle_hash.as_ref()),
+            Program::from_int(300),
+        ]);
+        let ownership_layer = module(NFT_OWNERSHIP_LAYER).curry(&[
+            Program::atom(NFT_OWNERSHIP_LAYER_HASH.as_ref()),
+            Program::atom(owner_did.as_ref()),
+            transfer_program,
+            standard(),
+        ]);
+        let nft = singleton(nft_state_layer(metadata.clone(), ownership_layer));
+        assert_eq!(PuzzleKind::identify(&nft), PuzzleKind::Nft {
+            launcher_id: LAUNCHER_ID,
+            metadata: metadata.clone(),
+            metadata_updater_hash: NFT_METADATA_UPDATER_HASH,
+            owner_did: Some(owner_did),
+            royalty_puzzle_hash: Some(royalty_puzzle_hash),
+            royalty_percentage: Some(300),
+            inner_puzzle: standard_kind(),
+        });
+
+        // An NFT without an ownership layer has no owner or royalty.
+        let nft = singleton(nft_state_layer(metadata.clone(), standard()));
+        assert_eq!(PuzzleKind::identify(&nft), PuzzleKind::Nft {
+            launcher_id: LAUNCHER_ID,
+            metadata,
+            metadata_updater_hash: NFT_METADATA_UPDATER_HASH,
+            owner_did: None,
+            royalty_puzzle_hash: None,
+            royalty_percentage: None,
+            inner_puzzle: standard_kind(),
+        });
+    }
+
+    #[test]
+    fn identify_did_and_datalayer() {
+        let metadata = Program::list([Program::cons(
+            Program::atom(b"name".to_vec()),
+            Program::atom(b"alice".to_vec()),
+        )]);
+        let did = singleton(module(DID_INNER_PUZZLE).curry(&[
+            standard(),
+            Program::nil(),
+            Program::from_int(1),
+            singleton_struct(),
+            metadata.clone(),
+        ]));
+        assert_eq!(PuzzleKind::identify(&did), PuzzleKind::Did {
+            launcher_id: LAUNCHER_ID,
+            recovery_list_hash: None,
+            num_verifications_required: 1,
+            metadata,
+            inner_puzzle: standard_kind(),
+        });
+
+        let root_hash = Bytes32::new([0x55; 32]);
+        let store = singleton(nft_state_layer(
+            Program::atom(root_hash.as_ref()),
+            standard(),
+        ));
+        assert_eq!(PuzzleKind::identify(&store), PuzzleKind::DataLayer {
+            launcher_id: LAUNCHER_ID,
+            root_hash: Some(root_hash),
+            inner_puzzle: standard_kind(),
+        });
+        let empty_store = singleton(nft_state_layer(Program::nil(), standard()));
+        assert_eq!(PuzzleKind::identify(&empty_store), PuzzleKind::DataLayer {
+            launcher_id: LAUNCHER_ID,
+            root_hash: None,
+            inner_puzzle: standard_kind(),
+        });
+    }
+
+    #[test]
+    fn identify_rejects_malformed_arguments() {
+        let unknown = |puzzle: &Program| PuzzleKind::Unknown {
+            puzzle_hash: puzzle.tree_hash().into(),
+        };
+        let malformed = [
+            // The standard puzzle curried with an extra argument.
+            module(STANDARD_PUZZLE).curry(&[Program::atom(SYNTHETIC_KEY), Program::nil()]),
+            // A CAT whose tail hash is not 32 bytes.
+            module(CAT_PUZZLE).curry(&[
+                Program::atom(CAT_PUZZLE_HASH.as_ref()),
+                Program::atom([0x22; 31]),
+                standard(),
+            ]),
+            // A singleton whose launcher ID is not 32 bytes.
+            module(SINGLETON_TOP_LAYER).curry(&[
+                Program::cons(
+                    Program::atom(SINGLETON_TOP_LAYER_HASH.as_ref()),
+                    Program::cons(Program::atom([0x11; 33]), Program::nil()),
+                ),
+                standard(),
+            ]),
+            // A DataLayer store whose root is not 32 bytes.
+            singleton(nft_state_layer(Program::atom([0x55; 3]), standard())),
+            Program::atom(b"not a puzzle".to_vec()),
+        ];
+        for puzzle in &malformed {
+            assert_eq!(PuzzleKind::identify(puzzle), unknown(puzzle));
+        }
+    }
+
+    #[test]
+    fn serialized_puzzles_match_their_hashes() {
+        for (puzzle, hash) in [
+            (SINGLETON_LAUNCHER, SINGLETON_LAUNCHER_HASH),
+            (DID_INNER_PUZZLE, DID_INNER_PUZZLE_HASH),
+            (STANDARD_PUZZLE, STANDARD_PUZZLE_HASH),
+            (CAT_PUZZLE, CAT_PUZZLE_HASH),
+            (CAT_PUZZLE_V1, CAT_PUZZLE_V1_HASH),
+            (SINGLETON_TOP_LAYER, SINGLETON_TOP_LAYER_HASH),
+            (NFT_STATE_LAYER, NFT_STATE_LAYER_HASH),
+            (NFT_OWNERSHIP_LAYER, NFT_OWNERSHIP_LAYER_HASH),
+            (
+                NFT_ROYALTY_TRANSFER_PROGRAM,
+                NFT_ROYALTY_TRANSFER_PROGRAM_HASH,
+            ),
+            (NFT_METADATA_UPDATER, NFT_METADATA_UPDATER_HASH),
+            (SETTLEMENT_PAYMENTS, SETTLEMENT_PAYMENTS_HASH),
+            (SETTLEMENT_PAYMENTS_V1, SETTLEMENT_PAYMENTS_V1_HASH),
+        ] {
+            assert_eq!(Program::from_bytes(puzzle).unwrap().tree_hash(), hash);
+        }
+    }
+}
@@ -0,0 +1,587 @@
+use std::{
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign, Sub},
+    str::FromStr,
+};
+
+use bech32::{convert_bits, ToBase32, Variant};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Error;
+
+macro_rules! bytes32_type {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name([u8; 32]);
+
+        impl $name {
+            pub const fn new(bytes: [u8; 32]) -> Self {
+                Self(bytes)
+            }
+
+            pub fn as_bytes(&self) -> &[u8; 32] {
+                &self.0
+            }
+
+            pub fn to_bytes(self) -> [u8; 32] {
+                self.0
+            }
+        }
+
+        impl From<[u8; 32]> for $name {
+            fn from(bytes: [u8; 32]) -> Self {
+                Self(bytes)
+            }
+        }
+
+        impl From<$name> for [u8; 32] {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        impl TryFrom<&[u8]> for $name {
+            type Error = Error;
+
+            fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+                let bytes: [u8; 32] = bytes
+                    .try_into()
+                    .map_err(|_| Error::HexDecodingError(hex::FromHexError::InvalidStringLength))?;
+                Ok(Self(bytes))
+            }
+        }
+
+        /// Parses 32 bytes of hex, with or without a `0x` prefix.
+        impl FromStr for $name {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let hex = s.strip_prefix("0x").unwrap_or(s);
+                let mut bytes = [0u8; 32];
+                hex::decode_to_slice(hex, &mut bytes)?;
+                Ok(Self(bytes))
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "0x{}", hex::encode(self.0))
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}({})", stringify!($name), self)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let s = String::deserialize(deserializer)?;
+                s.parse().map_err(de::Error::custom)
+            }
+        }
+    };
+}
+
+bytes32_type!(
+    /// Any 32 byte hash: header hashes, tree roots, store and launcher IDs.
+    Bytes32
+);
+bytes32_type!(
+    /// The tree hash of a puzzle, i.e. what an [`Address`] encodes.
+    PuzzleHash
+);
+bytes32_type!(
+    /// The name of a coin: `sha256(parent_coin_info + puzzle_hash + amount)`.
+    CoinId
+);
+bytes32_type!(
+    /// The hash of a CAT's TAIL program, which identifies the asset.
+    AssetId
+);
+
+macro_rules! bytes32_conversions {
+    ($($a:ident <-> $b:ident),*) => {
+        $(
+            impl From<$a> for $b {
+                fn from(value: $a) -> Self {
+                    Self(value.0)
+                }
+            }
+
+            impl From<$b> for $a {
+                fn from(value: $b) -> Self {
+                    Self(value.0)
+                }
+            }
+        )*
+    };
+}
+
+bytes32_conversions!(Bytes32 <-> PuzzleHash, Bytes32 <-> CoinId, Bytes32 <-> AssetId);
+
+/// A bech32m encoded puzzle hash together with its network prefix, e.g. `xch1...`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Address {
+    prefix: String,
+    puzzle_hash: PuzzleHash,
+}
+
+impl Address {
+    /// Fails with [`Error::InvalidAddressPrefix`] unless `prefix` is a valid bech32 human
+    /// readable part, so every `Address` can be displayed.
+    pub fn new(puzzle_hash: PuzzleHash, prefix: &str) -> Result<Self, Error> {
+        let data = puzzle_hash.as_bytes().to_base32();
+        if prefix.is_empty() || bech32::encode(prefix, data, Variant::Bech32m).is_err() {
+            return Err(Error::InvalidAddressPrefix(prefix.to_string()));
+        }
+        Ok(Self {
+            prefix: prefix.to_string(),
+            puzzle_hash,
+        })
+    }
+
+    /// Parses an address and checks that it carries `expected_prefix`, e.g. `txch`.
+    pub fn parse_with_prefix(address: &str, expected_prefix: &str) -> Result<Self, Error> {
+        let address: Address = address.parse()?;
+        if address.prefix != expected_prefix {
+            return Err(Error::WrongNetwork {
+                expected: expected_prefix.to_string(),
+                found: address.prefix,
+            });
+        }
+        Ok(address)
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn puzzle_hash(&self) -> PuzzleHash {
+        self.puzzle_hash
+    }
+}
+
+/// Parses and validates any address: it must be bech32m with a 32 byte payload. Use
+/// [`Address::parse_with_prefix`] or [`crate::network::Network::parse_address`] to also check
+/// the network.
+impl FromStr for Address {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (prefix, data, variant) = bech32::decode(s).map_err(|error| match error {
+            bech32::Error::InvalidChecksum => Error::InvalidAddressChecksum(s.to_string()),
+            error => Error::Bech32DecodingError(error),
+        })?;
+        if variant != Variant::Bech32m {
+            return Err(Error::InvalidAddressVariant(s.to_string()));
+        }
+        let bytes = convert_bits(&data, 5, 8, false)?;
+        let puzzle_hash: [u8; 32] = bytes
+            .as_slice()
+            .try_into()
+            .map_err(|_| Error::InvalidAddressLength(bytes.len()))?;
+        Ok(Self {
+            prefix,
+            puzzle_hash: PuzzleHash::new(puzzle_hash),
+        })
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let data = self.puzzle_hash.as_bytes().to_base32();
+        let encoded = bech32::encode(&self.prefix, data, Variant::Bech32m)
+            .expect("prefix validated on construction");
+        f.write_str(&encoded)
+    }
+}
+
+impl Serialize for Address {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Address {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+/// An amount in mojos, the smallest unit of XCH (and of every CAT).
+#[derive(
+    Default, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct Mojos(pub u64);
+
+impl Mojos {
+    pub const ZERO: Mojos = Mojos(0);
+
+    /// Parses a decimal XCH amount such as `"0.0000005"` without going through floats.
+    pub fn from_xch(amount: &str) -> Result<Mojos, Error> {
+        Self::from_decimal(amount, Unit::Xch)
+    }
+
+    /// Parses a decimal CAT amount, where one CAT is 1000 mojos.
+    pub fn from_cat(amount: &str) -> Result<Mojos, Error> {
+        Self::from_decimal(amount, Unit::Cat)
+    }
+
+    pub fn from_decimal(amount: &str, unit: Unit) -> Result<Mojos, Error> {
+        let decimals = unit.decimals();
+        let trimmed = amount.trim();
+        let (whole, fraction) = trimmed.split_once('.').unwrap_or((trimmed, ""));
+        let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+        if (whole.is_empty() && fraction.is_empty()) || !is_digits(whole) || !is_digits(fraction) {
+            return Err(Error::InvalidAmount(amount.to_string()));
+        }
+        if fraction.len() > decimals as usize {
+            return Err(Error::AmountPrecision(amount.to_string(), decimals));
+        }
+        let overflow = || Error::AmountOverflow(amount.to_string());
+        let whole = match whole {
+            "" => 0,
+            w => w.parse::<u64>().map_err(|_| overflow())?,
+        };
+        let fraction = format!("{:0<width$}", fraction, width = decimals as usize);
+        let fraction = match fraction.as_str() {
+            "" => 0,
+            f => f.parse::<u64>().map_err(|_| overflow())?,
+        };
+        whole
+            .checked_mul(unit.mojos_per_unit())
+            .and_then(|w| w.checked_add(fraction))
+            .map(Mojos)
+            .ok_or_else(overflow)
+    }
+
+    /// Formats the exact amount in `unit`, without trailing zeros, e.g. `"0.0000005"`.
+    pub fn to_decimal(self, unit: Unit) -> String {
+        let per_unit = unit.mojos_per_unit();
+        let (whole, fraction) = (self.0 / per_unit, self.0 % per_unit);
+        if fraction == 0 {
+            return whole.to_string();
+        }
+        let fraction = format!("{:0>width$}", fraction, width = unit.decimals() as usize);
+        format!("{}.{}", whole, fraction.trim_end_matches('0'))
+    }
+
+    pub fn to_xch(self) -> String {
+        self.to_decimal(Unit::Xch)
+    }
+
+    pub fn to_cat(self) -> String {
+        self.to_decimal(Unit::Cat)
+    }
+
+    /// Returns a [`Display`](fmt::Display) adapter that renders the amount in `unit` followed by
+    /// the unit symbol. A formatting precision pads the fractional part, e.g. `{:.12}`.
+    pub fn display(self, unit: Unit) -> MojosDisplay {
+        MojosDisplay { amount: self, unit }
+    }
+
+    pub fn checked_add(self, other: Mojos) -> Option<Mojos> {
+        self.0.checked_add(other.0).map(Mojos)
+    }
+
+    pub fn checked_sub(self, other: Mojos) -> Option<Mojos> {
+        self.0.checked_sub(other.0).map(Mojos)
+    }
+
+    pub fn checked_mul(self, factor: u64) -> Option<Mojos> {
+        self.0.checked_mul(factor).map(Mojos)
+    }
+}
+
+impl From<u64> for Mojos {
+    fn from(value: u64) -> Self {
+        Mojos(value)
+    }
+}
+
+impl From<Mojos> for u64 {
+    fn from(value: Mojos) -> Self {
+        value.0
+    }
+}
+
+/// Panics on overflow, in release builds too. Use [`Mojos::checked_add`] where an overflow is
+/// an error to handle, e.g. for amounts read from untrusted spends.
+impl Add for Mojos {
+    type Output = Mojos;
+
+    fn add(self, other: Mojos) -> Mojos {
+        self.checked_add(other).expect("Mojos addition overflowed")
+    }
+}
+
+impl AddAssign for Mojos {
+    fn add_assign(&mut self, other: Mojos) {
+        *self = *self + other;
+    }
+}
+
+/// Panics on underflow, in release builds too. Use [`Mojos::checked_sub`] where an underflow
+/// is an error to handle.
+impl Sub for Mojos {
+    type Output = Mojos;
+
+    fn sub(self, other: Mojos) -> Mojos {
+        self.checked_sub(other)
+            .expect("Mojos subtraction underflowed")
+    }
+}
+
+impl Sum for Mojos {
+    fn sum<I: Iterator<Item = Mojos>>(iter: I) -> Self {
+        iter.fold(Mojos::ZERO, Add::add)
+    }
+}
+
+impl<'a> Sum<&'a Mojos> for Mojos {
+    fn sum<I: Iterator<Item = &'a Mojos>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl fmt::Display for Mojos {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Unit {
+    Mojo,
+    #[default]
+    Xch,
+    Cat,
+}
+
+impl Unit {
+    pub fn decimals(self) -> u32 {
+        match self {
+            Unit::Mojo => 0,
+            Unit::Xch => 12,
+            Unit::Cat => 3,
+        }
+    }
+
+    pub fn mojos_per_unit(self) -> u64 {
+        10u64.pow(self.decimals())
+    }
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Unit::Mojo => "mojos",
+            Unit::Xch => "XCH",
+            Unit::Cat => "CAT",
+        }
+    }
+}
+
+pub struct MojosDisplay {
+    amount: Mojos,
+    unit: Unit,
+}
+
+impl fmt::Display for MojosDisplay {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut amount = self.amount.to_decimal(self.unit);
+        if let Some(precision) = f.precision() {
+            let decimals = amount.split_once('.').map_or(0, |(_, d)| d.len());
+            if decimals < precision {
+                if decimals == 0 {
+                    amount.push('.');
+                }
+                amount.extend(std::iter::repeat_n('0', precision - decimals));
+            }
+        }
+        write!(f, "{} {}", amount, self.unit.symbol())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mojos_arithmetic() {
+        assert_eq!(Mojos(2) + Mojos(3), Mojos(5));
+        assert_eq!(Mojos(5) - Mojos(3), Mojos(2));
+        let mut total = Mojos(1);
+        total += Mojos(1);
+        assert_eq!(total, Mojos(2));
+        assert_eq!([Mojos(1), Mojos(2)].iter().sum::<Mojos>(), Mojos(3));
+    }
+
+    #[test]
+    #[should_panic(expected = "overflowed")]
+    fn mojos_addition_panics_on_overflow() {
+        let _ = Mojos(u64::MAX) + Mojos(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "underflowed")]
+    fn mojos_subtraction_panics_on_underflow() {
+        let _ = Mojos(3) - Mojos(5);
+    }
+
+    #[test]
+    fn mojos_checked_arithmetic() {
+        assert_eq!(Mojos(u64::MAX).checked_add(Mojos(1)), None);
+        assert_eq!(Mojos(3).checked_sub(Mojos(5)), None);
+        assert_eq!(Mojos(5).checked_sub(Mojos(3)), Some(Mojos(2)));
+    }
+
+    #[test]
+    fn mojos_from_decimal() {
+        assert_eq!(
+            Mojos::from_decimal("1.5", Unit::Xch).unwrap(),
+            Mojos(1_500_000_000_000)
+        );
+        assert_eq!(
+            Mojos::from_decimal("0.000000000001", Unit::Xch).unwrap(),
+            Mojos(1)
+        );
+        assert_eq!(Mojos::from_decimal("1.5", Unit::Cat).unwrap(), Mojos(1_500));
+        assert_eq!(Mojos::from_decimal("42", Unit::Mojo).unwrap(), Mojos(42));
+        assert_eq!(Mojos::from_decimal(".5", Unit::Cat).unwrap(), Mojos(500));
+        assert_eq!(Mojos::from_decimal("2.", Unit::Cat).unwrap(), Mojos(2_000));
+        assert_eq!(
+            Mojos::from_decimal(" 0.25\n", Unit::Cat).unwrap(),
+            Mojos(250)
+        );
+        assert_eq!(
+            Mojos::from_decimal("18446744073709551615", Unit::Mojo).unwrap(),
+            Mojos(u64::MAX)
+        );
+
+        for amount in ["", " ", ".", "-1", "+1", "1 000", "1.2.3", "1e3", "0x10"] {
+            assert!(
+                matches!(
+                    Mojos::from_decimal(amount, Unit::Xch),
+                    Err(Error::InvalidAmount(_))
+                ),
+                "{:?}",
+                amount
+            );
+        }
+        for (amount, unit) in [
+            ("0.0000000000001", Unit::Xch),
+            ("0.0001", Unit::Cat),
+            ("1.0", Unit::Mojo),
+        ] {
+            assert!(matches!(
+                Mojos::from_decimal(amount, unit),
+                Err(Error::AmountPrecision(_, decimals)) if decimals == unit.decimals()
+            ));
+        }
+        for (amount, unit) in [
+            ("18446744073709551616", Unit::Mojo),
+            ("18446744.073709551616", Unit::Xch),
+            ("18446744073709552", Unit::Cat),
+        ] {
+            assert!(
+                matches!(
+                    Mojos::from_decimal(amount, unit),
+                    Err(Error::AmountOverflow(_))
+                ),
+                "{}",
+                amount
+            );
+        }
+    }
+
+    #[test]
+    fn mojos_to_decimal() {
+        assert_eq!(Mojos(1_500_000_000_000).to_decimal(Unit::Xch), "1.5");
+        assert_eq!(Mojos(500_000).to_decimal(Unit::Xch), "0.0000005");
+        assert_eq!(Mojos(2_000_000_000_000).to_decimal(Unit::Xch), "2");
+        assert_eq!(Mojos(1_234).to_decimal(Unit::Cat), "1.234");
+        assert_eq!(Mojos(1_234).to_decimal(Unit::Mojo), "1234");
+        assert_eq!(Mojos::ZERO.to_decimal(Unit::Cat), "0");
+
+        for unit in [Unit::Mojo, Unit::Xch, Unit::Cat] {
+            for amount in [
+                Mojos::ZERO,
+                Mojos(1),
+                Mojos(1_000),
+                Mojos(123_456_789),
+                Mojos(u64::MAX),
+            ] {
+                assert_eq!(
+                    Mojos::from_decimal(&amount.to_decimal(unit), unit).unwrap(),
+                    amount
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn address_from_str_rejects_invalid_addresses() {
+        let encode =
+            |data: &[u8], variant| bech32::encode("xch", data.to_base32(), variant).unwrap();
+        let valid = encode(&[7; 32], Variant::Bech32m);
+        assert_eq!(
+            valid.parse::<Address>().unwrap(),
+            Address::new(PuzzleHash::new([7; 32]), "xch").unwrap()
+        );
+
+        assert!(matches!(
+            encode(&[7; 32], Variant::Bech32).parse::<Address>(),
+            Err(Error::InvalidAddressVariant(_))
+        ));
+        for length in [31, 33] {
+            assert!(matches!(
+                encode(&vec![7; length], Variant::Bech32m).parse::<Address>(),
+                Err(Error::InvalidAddressLength(found)) if found == length
+            ));
+        }
+
+        let mut corrupted = valid.clone();
+        let last = corrupted.pop().unwrap();
+        corrupted.push(if last == 'q' { 'p' } else { 'q' });
+        assert!(matches!(
+            corrupted.parse::<Address>(),
+            Err(Error::InvalidAddressChecksum(_))
+        ));
+
+        assert!(matches!(
+            Address::parse_with_prefix(&valid, "txch"),
+            Err(Error::WrongNetwork { .. })
+        ));
+        assert!(Address::parse_with_prefix(&valid, "xch").is_ok());
+    }
+
+    #[test]
+    fn address_rejects_invalid_prefixes() {
+        let puzzle_hash = PuzzleHash::new([7; 32]);
+        for prefix in ["", "x ch", "x\u{e9}ch"] {
+            assert!(matches!(
+                Address::new(puzzle_hash, prefix),
+                Err(Error::InvalidAddressPrefix(_))
+            ));
+        }
+        let address = Address::new(puzzle_hash, "txch").unwrap();
+        assert_eq!(address.to_string().parse::<Address>().unwrap(), address);
+    }
+}
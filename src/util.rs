@@ -6,15 +6,22 @@ use std::{
 
 use anyhow::Result;
 use bech32::{self, convert_bits, u5, Variant};
-use chrono::{DateTime, NaiveDateTime, Utc};
+use chrono::{DateTime, Utc};
 use hex::ToHex;
-#[cfg(any(feature = "assemble", feature = "disassemble"))]
+#[cfg(any(feature = "assemble", feature = "curry"))]
 use pyo3::{prelude::*, types::IntoPyDict};
 use reqwest::Identity;
-use serde::de::{Deserialize, Deserializer};
+use serde::{
+    de::{self, Deserialize, Deserializer},
+    Serializer,
+};
 use tokio::fs::read;
 
-use crate::Error;
+use crate::{
+    clvm::Program,
+    types::{Address, Mojos},
+    Error,
+};
 
 pub async fn load_pem_pair(
     key: impl AsRef<Path>,
@@ -25,11 +32,10 @@ pub async fn load_pem_pair(
     Ok(Identity::from_pem(&buf)?)
 }
 
-pub fn decode_puzzle_hash(puzzle_hash: &str) -> Result<String> {
-    let (_hrp, data, _variant) = bech32::decode(puzzle_hash)?;
-    let decoded = convert_bits(&data, 5, 8, false);
-    let hex = decoded?.encode_hex::<String>();
-    Ok(hex)
+/// Decodes an address of any network into its puzzle hash as hex.
+pub fn decode_puzzle_hash(address: &str) -> Result<String> {
+    let address: Address = address.parse()?;
+    Ok(address.puzzle_hash().as_bytes().encode_hex::<String>())
 }
 
 pub fn encode_puzzle_hash(puzzle_hash: &str, prefix: &str) -> Result<String, Error> {
@@ -43,33 +49,75 @@ pub fn encode_puzzle_hash(puzzle_hash: &str, prefix: &str) -> Result<String, Err
     Ok(encoded)
 }
 
-pub fn mojo_to_xch(amount: u64) -> f64 {
-    amount as f64 / 1_000_000_000_000.0
+pub fn strip_hex_prefix(hex: &str) -> &str {
+    hex.strip_prefix("0x").unwrap_or(hex)
+}
+
+pub fn decode_hex(hex: &str) -> Result<Vec<u8>, Error> {
+    Ok(hex::decode(strip_hex_prefix(hex))?)
 }
 
-pub fn xch_to_mojo(amount: f64) -> u64 {
-    (amount * 1_000_000_000_000.0) as u64
+/// Encodes `value` the way CLVM encodes integer atoms: big-endian, minimal length, with a
+/// leading zero byte when the high bit would otherwise mark the number as negative.
+pub fn int_to_bytes(value: u64) -> Vec<u8> {
+    if value == 0 {
+        return Vec::new();
+    }
+    let bytes = value.to_be_bytes();
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    let mut encoded = Vec::with_capacity(9);
+    if bytes[start] & 0x80 != 0 {
+        encoded.push(0);
+    }
+    encoded.extend_from_slice(&bytes[start..]);
+    encoded
+}
+
+pub fn mojo_to_xch(amount: Mojos) -> String {
+    amount.to_xch()
+}
+
+pub fn xch_to_mojo(amount: &str) -> Result<Mojos, Error> {
+    Mojos::from_xch(amount)
+}
+
+pub fn humanize_bytes(bytes: u128) -> String {
+    const UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.3} {}", value, UNITS[unit])
 }
 
 pub(crate) fn deserialize_optional_timestamp<'de, D: Deserializer<'de>>(
     d: D,
 ) -> Result<Option<DateTime<Utc>>, D::Error> {
     match Option::<i64>::deserialize(d)? {
-        Some(ts) => Ok(Some(DateTime::<Utc>::from_utc(
-            NaiveDateTime::from_timestamp_opt(ts, 0).unwrap(),
-            Utc,
-        ))),
+        Some(ts) => Ok(DateTime::<Utc>::from_timestamp(ts, 0)),
         None => Ok(None),
     }
 }
 
-pub(crate) fn deserialize_empty_vec_to_none<'de, D>(
+/// Serializes bytes as hex without a `0x` prefix, as the RPCs send raw message bytes.
+pub(crate) fn serialize_hex<S: Serializer>(bytes: &[u8], s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(&hex::encode(bytes))
+}
+
+pub(crate) fn deserialize_hex<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<u8>, D::Error> {
+    decode_hex(&String::deserialize(d)?).map_err(de::Error::custom)
+}
+
+pub(crate) fn deserialize_empty_vec_to_none<'de, D, T>(
     deserializer: D,
-) -> Result<Option<Vec<String>>, D::Error>
+) -> Result<Option<Vec<T>>, D::Error>
 where
     D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
 {
-    let vec: Vec<String> = Deserialize::deserialize(deserializer)?;
+    let vec: Vec<T> = Deserialize::deserialize(deserializer)?;
     if vec.is_empty() {
         Ok(None)
     } else {
@@ -77,8 +125,23 @@ where
     }
 }
 
+/// Disassembles a serialized program given as hex into the `clvm_tools` text form.
+pub fn disassemble(program: &str) -> Result<String, Error> {
+    Ok(Program::from_hex(program)?.disassemble())
+}
+
+/// Uncurries a serialized program given as hex, returning the module and curried arguments
+/// as serialized hex.
+pub fn uncurry(program: &str) -> Result<(String, Vec<String>), Error> {
+    let (module, args) = Program::from_hex(program)?
+        .uncurry()
+        .ok_or_else(|| Error::ClvmError("program is not curried".to_string()))?;
+    Ok((module.to_hex(), args.iter().map(Program::to_hex).collect()))
+}
+
+/// [`disassemble`] through Python's `clvm_tools`, kept to cross-check the native version.
 #[cfg(feature = "assemble")]
-pub fn disassemble(program: &str) -> PyResult<String> {
+pub fn py_disassemble(program: &str) -> PyResult<String> {
     pyo3::prepare_freethreaded_python();
     Python::with_gil(|py| {
         let locals = [("program", program)].into_py_dict(py);
@@ -99,8 +162,10 @@ disassembled_program = disassemble(parsed_program)
     })
 }
 
+/// [`uncurry`] through Python's `clvm_tools`, kept to cross-check the native version. Note
+/// that Python renders programs as hex without the `0x` prefix.
 #[cfg(feature = "curry")]
-pub fn uncurry(program: &str) -> PyResult<(String, Vec<String>)> {
+pub fn py_uncurry(program: &str) -> PyResult<(String, Vec<String>)> {
     pyo3::prepare_freethreaded_python();
     Python::with_gil(|py| {
         let locals = [("program", program)].into_py_dict(py);
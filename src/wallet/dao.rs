@@ -0,0 +1,146 @@
+//! The DAO wallet endpoints, reached through [`super::Rpc::dao`].
+
+use crate::prelude::*;
+
+pub struct Rpc<'a> {
+    pub(super) client: &'a Client,
+}
+
+impl Rpc<'_> {
+    /// The treasury's balances, keyed by `xch` or the CAT asset ID. `dao_get_treasury_balance`.
+    pub async fn get_treasury_balance(&self, wallet_id: u64) -> Result<HashMap<String, Mojos>> {
+        let json = json!({
+            "wallet_id": wallet_id,
+        });
+        let res: DaoTreasuryBalanceResponse = self
+            .client
+            .cmd("dao_get_treasury_balance", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.balances {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Creates a proposal and votes for it with `vote_amount` DAO CATs, or all the wallet
+    /// has. Returns the proposal ID and the transaction. `dao_create_proposal`.
+    pub async fn create_proposal(
+        &self,
+        wallet_id: u64,
+        proposal: &DaoProposal,
+        vote_amount: Option<u64>,
+        fee: Mojos,
+    ) -> Result<(Bytes32, Transaction)> {
+        if let DaoProposal::Mint {
+            cat_target_address, ..
+        } = proposal
+        {
+            self.client.network.parse_address(cat_target_address)?;
+        }
+        let mut json = json!(proposal);
+        json["wallet_id"] = Value::from(wallet_id);
+        json["fee"] = json!(fee);
+        if let Some(vote_amount) = vote_amount {
+            json["vote_amount"] = Value::from(vote_amount);
+        }
+        let res: DaoTransactionResponse = self
+            .client
+            .cmd("dao_create_proposal", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match (res.proposal_id, res.tx) {
+            (Some(proposal_id), Some(tx)) => Ok((proposal_id, tx)),
+            _ => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Votes on a proposal with `vote_amount` DAO CATs, or all the wallet has.
+    /// `dao_vote_on_proposal`.
+    pub async fn vote_on_proposal(
+        &self,
+        wallet_id: u64,
+        proposal_id: &Bytes32,
+        vote_amount: Option<u64>,
+        is_yes_vote: bool,
+        fee: Mojos,
+    ) -> Result<Transaction> {
+        let mut json = json!({
+            "wallet_id": wallet_id,
+            "proposal_id": proposal_id,
+            "is_yes_vote": is_yes_vote,
+            "fee": fee,
+        });
+        if let Some(vote_amount) = vote_amount {
+            json["vote_amount"] = Value::from(vote_amount);
+        }
+        let res: DaoTransactionResponse = self
+            .client
+            .cmd("dao_vote_on_proposal", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.tx {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Lists the DAO's proposals, with the rules that decide when they can close.
+    /// `dao_get_proposals`.
+    pub async fn get_proposals(
+        &self,
+        wallet_id: u64,
+        include_closed: bool,
+    ) -> Result<DaoProposals> {
+        let json = json!({
+            "wallet_id": wallet_id,
+            "include_closed": include_closed,
+        });
+        let res: DaoProposalsResponse = self
+            .client
+            .cmd("dao_get_proposals", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match (res.proposals, res.proposal_timelock, res.soft_close_length) {
+            (Some(proposals), Some(proposal_timelock), Some(soft_close_length)) => {
+                Ok(DaoProposals {
+                    proposals,
+                    proposal_timelock,
+                    soft_close_length,
+                })
+            },
+            _ => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Closes a proposal, running it if it passed. With `self_destruct`, a proposal that can
+    /// no longer run is closed without running it. `dao_close_proposal`.
+    pub async fn close_proposal(
+        &self,
+        wallet_id: u64,
+        proposal_id: &Bytes32,
+        self_destruct: bool,
+        fee: Mojos,
+    ) -> Result<Transaction> {
+        let json = json!({
+            "wallet_id": wallet_id,
+            "proposal_id": proposal_id,
+            "self_destruct": self_destruct,
+            "fee": fee,
+        });
+        let res: DaoTransactionResponse = self
+            .client
+            .cmd("dao_close_proposal", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.tx {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+}
@@ -0,0 +1,1909 @@
+pub mod dao;
+pub mod vc;
+
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::time::{sleep, Instant};
+
+use crate::{fullnode, prelude::*};
+
+/// How [`Rpc::wait_for_confirmation`] polls.
+#[derive(Clone)]
+pub struct ConfirmationOptions {
+    /// How long to wait before giving up with [`Error::ConfirmationTimeout`].
+    pub timeout: Duration,
+    /// The wait before the second poll. Each later wait is `backoff` times longer, up to
+    /// `max_poll_interval`.
+    pub poll_interval: Duration,
+    pub max_poll_interval: Duration,
+    pub backoff: f64,
+    /// The blocks that must be in the chain, counting the one the transaction is in.
+    pub confirmations: u32,
+    /// The full node whose peak counts confirmations. Without one, the wallet's own height is
+    /// used, which can lag behind while it syncs.
+    pub full_node: Option<fullnode::Rpc>,
+}
+
+impl Default for ConfirmationOptions {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_secs(600),
+            poll_interval: Duration::from_secs(2),
+            max_poll_interval: Duration::from_secs(30),
+            backoff: 1.5,
+            confirmations: 1,
+            full_node: None,
+        }
+    }
+}
+
+impl ConfirmationOptions {
+    fn validate(&self) -> Result<(), Error> {
+        if !self.backoff.is_finite() || self.backoff < 1.0 {
+            return Err(Error::InvalidConfirmationOptions(format!(
+                "backoff must be a finite number of at least 1, got {}",
+                self.backoff
+            )));
+        }
+        if self.poll_interval.is_zero() {
+            return Err(Error::InvalidConfirmationOptions(
+                "poll_interval must be positive".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// The wait after `interval`, which saturates at `max_poll_interval` instead of
+    /// overflowing `Duration`.
+    fn next_interval(&self, interval: Duration) -> Duration {
+        Duration::try_from_secs_f64(interval.as_secs_f64() * self.backoff)
+            .unwrap_or(Duration::MAX)
+            .min(self.max_poll_interval)
+    }
+}
+
+#[derive(Clone)]
+pub struct Rpc {
+    pub client: Client,
+}
+
+impl Rpc {
+    pub fn init(client: Client) -> Self {
+        Self { client }
+    }
+
+    /// The Verifiable Credential endpoints.
+    pub fn vc(&self) -> vc::Rpc<'_> {
+        vc::Rpc {
+            client: &self.client,
+        }
+    }
+
+    /// The DAO wallet endpoints.
+    pub fn dao(&self) -> dao::Rpc<'_> {
+        dao::Rpc {
+            client: &self.client,
+        }
+    }
+
+    pub async fn get_routes(&self) -> Result<Vec<String>> {
+        let res: RoutesResponse = self.client.cmd("get_routes", None).await?.json().await?;
+        match res.routes {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn get_transaction(&self, tx_id: &Bytes32) -> Result<Transaction> {
+        let json = json!({
+        "transaction_id": tx_id,
+        });
+        let res: TransactionResponse = self
+            .client
+            .cmd("get_transaction", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+
+        match res.transaction {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Polls a transaction until it is confirmed `options.confirmations` deep and returns it.
+    /// Without `options.full_node`, depth is counted from the wallet's synced height, so the
+    /// wait can outlast the real confirmations while the wallet catches up.
+    /// Fails with [`Error::TransactionFailed`] once [`Transaction::rejection`] gives up on it, with
+    /// [`Error::TransactionRemoved`] if the wallet dropped it, and with
+    /// [`Error::ConfirmationTimeout`] after `options.timeout`. Options with a `backoff` below 1
+    /// or not finite, or a zero `poll_interval`, fail with
+    /// [`Error::InvalidConfirmationOptions`] before anything is polled.
+    pub async fn wait_for_confirmation(
+        &self,
+        tx_id: &Bytes32,
+        options: &ConfirmationOptions,
+    ) -> Result<Transaction> {
+        options.validate()?;
+        let deadline = Instant::now() + options.timeout;
+        let mut interval = options.poll_interval;
+        loop {
+            let json = json!({
+                "transaction_id": tx_id,
+            });
+            let res: TransactionResponse = self
+                .client
+                .cmd("get_transaction", Some(json.to_string()))
+                .await?
+                .json()
+                .await?;
+            let Some(transaction) = res.transaction else {
+                return Err(match res.error {
+                    Some(e) if e.contains("not found") => Error::TransactionRemoved(*tx_id).into(),
+                    e => anyhow!("{:#?}", e),
+                });
+            };
+            if transaction.confirmed {
+                // The block holding the transaction is its first confirmation.
+                let needed =
+                    transaction.confirmed_at_height as u64 + u64::from(options.confirmations);
+                if options.confirmations <= 1 || self.peak_height(options).await? + 1 >= needed {
+                    return Ok(transaction);
+                }
+            } else if let Some(reason) = transaction.rejection() {
+                return Err(Error::TransactionFailed(*tx_id, reason.to_string()).into());
+            }
+
+            let now = Instant::now();
+            if now >= deadline {
+                return Err(Error::ConfirmationTimeout(*tx_id, options.timeout).into());
+            }
+            sleep(interval.min(deadline - now)).await;
+            interval = options.next_interval(interval);
+        }
+    }
+
+    async fn peak_height(&self, options: &ConfirmationOptions) -> Result<u64> {
+        match &options.full_node {
+            Some(full_node) => Ok(full_node.get_blockchain_state().await?.peak.height),
+            None => Ok(u64::from(self.get_height_info().await?)),
+        }
+    }
+
+    /// Sends `amount` to `address`, which must belong to the client's network.
+    pub async fn send_transaction(
+        &self,
+        wallet_id: u64,
+        amount: Mojos,
+        address: &str,
+        fee: Mojos,
+        options: &SendTransactionOptions,
+    ) -> Result<Transaction> {
+        let address = self.client.network.parse_address(address)?;
+        let mut json = json!({
+            "wallet_id": wallet_id,
+            "amount": amount,
+            "address": address,
+            "fee": fee,
+        });
+        merge_options(&mut json, options)?;
+        let res: TransactionResponse = self
+            .client
+            .cmd("send_transaction", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.transaction {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Pays several puzzle hashes in a single spend bundle.
+    pub async fn send_transaction_multi(
+        &self,
+        wallet_id: u64,
+        additions: &[Addition],
+        fee: Mojos,
+        coin_selection: &CoinSelection,
+    ) -> Result<Transaction> {
+        let mut json = json!({
+            "wallet_id": wallet_id,
+            "additions": additions,
+            "fee": fee,
+        });
+        merge_options(&mut json, coin_selection)?;
+        let res: TransactionResponse = self
+            .client
+            .cmd("send_transaction_multi", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.transaction {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn get_transactions(
+        &self,
+        wallet_id: u64,
+        options: &GetTransactionsOptions,
+    ) -> Result<Vec<Transaction>> {
+        let mut json = json!({
+            "wallet_id": wallet_id,
+        });
+        merge_options(&mut json, options)?;
+        let res: TransactionsResponse = self
+            .client
+            .cmd("get_transactions", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.transactions {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn get_transaction_count(
+        &self,
+        wallet_id: u64,
+        type_filter: Option<&TransactionTypeFilter>,
+        confirmed: Option<bool>,
+    ) -> Result<u64> {
+        let mut json = json!({
+            "wallet_id": wallet_id,
+        });
+        if let Some(type_filter) = type_filter {
+            json["type_filter"] = json!(type_filter);
+        }
+        if let Some(confirmed) = confirmed {
+            json["confirmed"] = Value::from(confirmed);
+        }
+        let res: TransactionCountResponse = self
+            .client
+            .cmd("get_transaction_count", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.count {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn delete_unconfirmed_transactions(&self, wallet_id: u64) -> Result<()> {
+        let json = json!({
+            "wallet_id": wallet_id,
+        });
+        let res: BasicResponse = self
+            .client
+            .cmd("delete_unconfirmed_transactions", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.error {
+            Some(e) => Err(anyhow!("{:#?}", e)),
+            None => Ok(()),
+        }
+    }
+
+    /// Claims incoming clawback coins, or claws back outgoing ones, returning the IDs of the
+    /// transactions that were pushed. `batch_size` caps how many coins go in each spend bundle.
+    pub async fn spend_clawback_coins(
+        &self,
+        coin_ids: &[CoinId],
+        fee: Mojos,
+        batch_size: Option<u64>,
+        force: bool,
+    ) -> Result<Vec<Bytes32>> {
+        let mut json = json!({
+            "coin_ids": coin_ids,
+            "fee": fee,
+            "force": force,
+        });
+        if let Some(batch_size) = batch_size {
+            json["batch_size"] = Value::from(batch_size);
+        }
+        let res: TransactionIdsResponse = self
+            .client
+            .cmd("spend_clawback_coins", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.transaction_ids {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Sends `message` to the owner of `address` in a coin of `amount` at their puzzle hash.
+    /// Wallets ignore notifications worth less than their configured minimum.
+    pub async fn send_notification(
+        &self,
+        address: &str,
+        message: &[u8],
+        amount: Mojos,
+        fee: Mojos,
+    ) -> Result<Transaction> {
+        let address = self.client.network.parse_address(address)?;
+        let json = json!({
+            "target": address.puzzle_hash(),
+            "message": hex::encode(message),
+            "amount": amount,
+            "fee": fee,
+        });
+        let res: SendNotificationResponse = self
+            .client
+            .cmd("send_notification", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.tx {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Lists received notifications, or only those in `ids` when it is not empty. `start` and
+    /// `end` page through the list.
+    pub async fn get_notifications(
+        &self,
+        ids: &[CoinId],
+        start: Option<u32>,
+        end: Option<u32>,
+    ) -> Result<Vec<Notification>> {
+        let mut json = json!({});
+        if !ids.is_empty() {
+            json["ids"] = json!(ids);
+        }
+        if let Some(start) = start {
+            json["start"] = Value::from(start);
+        }
+        if let Some(end) = end {
+            json["end"] = Value::from(end);
+        }
+        let res: NotificationsResponse = self
+            .client
+            .cmd("get_notifications", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.notifications {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Deletes the notifications in `ids`, or all of them when `ids` is empty.
+    pub async fn delete_notifications(&self, ids: &[CoinId]) -> Result<()> {
+        let mut json = json!({});
+        if !ids.is_empty() {
+            json["ids"] = json!(ids);
+        }
+        let res: BasicResponse = self
+            .client
+            .cmd("delete_notifications", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.error {
+            Some(e) => Err(anyhow!("{:#?}", e)),
+            None => Ok(()),
+        }
+    }
+
+    pub async fn log_in(&self, fingerprint: u32) -> Result<u32> {
+        let json = json!({
+            "fingerprint": fingerprint,
+        });
+        let res: FingerprintResponse = self
+            .client
+            .cmd("log_in", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.fingerprint {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// The fingerprint of the key the wallet is logged in with, if any.
+    pub async fn get_logged_in_fingerprint(&self) -> Result<Option<u32>> {
+        let res: FingerprintResponse = self
+            .client
+            .cmd("get_logged_in_fingerprint", None)
+            .await?
+            .json()
+            .await?;
+        match res.error {
+            Some(e) => Err(anyhow!("{:#?}", e)),
+            None => Ok(res.fingerprint),
+        }
+    }
+
+    /// The fingerprints of every key in the keychain.
+    pub async fn get_public_keys(&self) -> Result<Vec<u32>> {
+        let res: PublicKeysResponse = self
+            .client
+            .cmd("get_public_keys", None)
+            .await?
+            .json()
+            .await?;
+        match res.public_key_fingerprints {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Fetches the secret key and mnemonic seed of a key. Only built with the `private-keys`
+    /// feature, so custody tooling has to opt in to handling secrets.
+    #[cfg(feature = "private-keys")]
+    pub async fn get_private_key(&self, fingerprint: u32) -> Result<PrivateKey> {
+        let json = json!({
+            "fingerprint": fingerprint,
+        });
+        let res: PrivateKeyResponse = self
+            .client
+            .cmd("get_private_key", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.private_key {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn generate_mnemonic(&self) -> Result<Vec<String>> {
+        let res: MnemonicResponse = self
+            .client
+            .cmd("generate_mnemonic", None)
+            .await?
+            .json()
+            .await?;
+        match res.mnemonic {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Imports a key from its mnemonic words and returns its fingerprint.
+    pub async fn add_key(&self, mnemonic: &[String]) -> Result<u32> {
+        let json = json!({
+            "mnemonic": mnemonic,
+        });
+        let res: FingerprintResponse = self
+            .client
+            .cmd("add_key", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.fingerprint {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn delete_key(&self, fingerprint: u32) -> Result<()> {
+        let json = json!({
+            "fingerprint": fingerprint,
+        });
+        let res: BasicResponse = self
+            .client
+            .cmd("delete_key", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.error {
+            Some(e) => Err(anyhow!("{:#?}", e)),
+            None => Ok(()),
+        }
+    }
+
+    /// Lists the wallets of the logged in key, optionally only those of one type.
+    pub async fn get_wallets(
+        &self,
+        wallet_type: Option<WalletType>,
+        include_data: bool,
+    ) -> Result<Vec<WalletInfo>> {
+        let mut json = json!({
+            "include_data": include_data,
+        });
+        if let Some(wallet_type) = wallet_type {
+            json["type"] = json!(wallet_type);
+        }
+        let res: WalletsResponse = self
+            .client
+            .cmd("get_wallets", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.wallets {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn get_sync_status(&self) -> Result<WalletSyncStatus> {
+        let res: WalletSyncStatusResponse = self
+            .client
+            .cmd("get_sync_status", None)
+            .await?
+            .json()
+            .await?;
+        match (res.synced, res.syncing) {
+            (Some(synced), Some(syncing)) => Ok(WalletSyncStatus {
+                synced,
+                syncing,
+                genesis_initialized: res.genesis_initialized.unwrap_or_default(),
+            }),
+            _ => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn get_height_info(&self) -> Result<u32> {
+        let res: HeightInfoResponse = self
+            .client
+            .cmd("get_height_info", None)
+            .await?
+            .json()
+            .await?;
+        match res.height {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Returns the wallet's current receive address, or derives an unused one if
+    /// `new_address` is set.
+    pub async fn get_next_address(&self, wallet_id: u64, new_address: bool) -> Result<Address> {
+        let json = json!({
+            "wallet_id": wallet_id,
+            "new_address": new_address,
+        });
+        let res: NextAddressResponse = self
+            .client
+            .cmd("get_next_address", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.address {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn cat_get_asset_id(&self, wallet_id: u64) -> Result<AssetId> {
+        let json = json!({
+            "wallet_id": wallet_id,
+        });
+        let res: CatAssetIdResponse = self
+            .client
+            .cmd("cat_get_asset_id", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.asset_id {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn cat_asset_id_to_name(&self, asset_id: &AssetId) -> Result<CatAssetName> {
+        let json = json!({
+            "asset_id": asset_id,
+        });
+        let res: CatNameResponse = self
+            .client
+            .cmd("cat_asset_id_to_name", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.name {
+            Some(name) => Ok(CatAssetName {
+                wallet_id: res.wallet_id,
+                name,
+            }),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn cat_get_name(&self, wallet_id: u64) -> Result<String> {
+        let json = json!({
+            "wallet_id": wallet_id,
+        });
+        let res: CatNameResponse = self
+            .client
+            .cmd("cat_get_name", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.name {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn cat_set_name(&self, wallet_id: u64, name: &str) -> Result<()> {
+        let json = json!({
+            "wallet_id": wallet_id,
+            "name": name,
+        });
+        let res: WalletIdResponse = self
+            .client
+            .cmd("cat_set_name", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.wallet_id {
+            Some(_) => Ok(()),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Creates a wallet tracking an existing CAT, or returns the one that already does.
+    pub async fn create_cat_wallet(
+        &self,
+        asset_id: &AssetId,
+        name: Option<&str>,
+    ) -> Result<CatWallet> {
+        let mut json = json!({
+            "wallet_type": "cat_wallet",
+            "mode": "existing",
+            "asset_id": asset_id,
+        });
+        if let Some(name) = name {
+            json["name"] = Value::from(name);
+        }
+        self.create_cat(json).await
+    }
+
+    /// Issues `amount` mojos of a new single issuance CAT and creates its wallet.
+    pub async fn create_new_cat(
+        &self,
+        amount: Mojos,
+        fee: Mojos,
+        name: Option<&str>,
+    ) -> Result<CatWallet> {
+        let mut json = json!({
+            "wallet_type": "cat_wallet",
+            "mode": "new",
+            "amount": amount,
+            "fee": fee,
+        });
+        if let Some(name) = name {
+            json["name"] = Value::from(name);
+        }
+        self.create_cat(json).await
+    }
+
+    async fn create_cat(&self, json: Value) -> Result<CatWallet> {
+        let res: CreateCatWalletResponse = self
+            .client
+            .cmd("create_new_wallet", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match (res.wallet_id, res.asset_id) {
+            (Some(wallet_id), Some(asset_id)) => Ok(CatWallet {
+                wallet_id,
+                asset_id,
+            }),
+            _ => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Sends `amount` mojos of the wallet's CAT to `address`, which must belong to the client's
+    /// network.
+    pub async fn cat_spend(
+        &self,
+        wallet_id: u64,
+        amount: Mojos,
+        address: &str,
+        fee: Mojos,
+        options: &SendTransactionOptions,
+    ) -> Result<Transaction> {
+        let address = self.client.network.parse_address(address)?;
+        let mut json = json!({
+            "wallet_id": wallet_id,
+            "amount": amount,
+            "inner_address": address,
+            "fee": fee,
+        });
+        merge_options(&mut json, options)?;
+        let res: TransactionResponse = self
+            .client
+            .cmd("cat_spend", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.transaction {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn get_stray_cats(&self) -> Result<Vec<StrayCat>> {
+        let res: StrayCatsResponse = self
+            .client
+            .cmd("get_stray_cats", None)
+            .await?
+            .json()
+            .await?;
+        match res.stray_cats {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Lists the NFTs of one NFT wallet, or of all of them, `num` at a time from `start_index`.
+    pub async fn nft_get_nfts(
+        &self,
+        wallet_id: Option<u64>,
+        start_index: u32,
+        num: u32,
+    ) -> Result<Vec<NftInfo>> {
+        let mut json = json!({
+            "start_index": start_index,
+            "num": num,
+        });
+        if let Some(wallet_id) = wallet_id {
+            json["wallet_id"] = Value::from(wallet_id);
+        }
+        let res: NftListResponse = self
+            .client
+            .cmd("nft_get_nfts", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.nft_list {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Looks up an NFT by its `nft1...` ID or current coin ID. With `latest`, the wallet
+    /// follows the singleton to its newest state.
+    pub async fn nft_get_info(&self, coin_id: &str, latest: bool) -> Result<NftInfo> {
+        let json = json!({
+            "coin_id": coin_id,
+            "latest": latest,
+        });
+        let res: NftInfoResponse = self
+            .client
+            .cmd("nft_get_info", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.nft_info {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn nft_mint_nft(
+        &self,
+        wallet_id: u64,
+        metadata: &NftMetadata,
+        options: &NftMintOptions,
+        fee: Mojos,
+    ) -> Result<MintedNft> {
+        for address in [&options.royalty_address, &options.target_address]
+            .into_iter()
+            .flatten()
+        {
+            self.client.network.parse_address(address)?;
+        }
+        let mut json = json!({
+            "wallet_id": wallet_id,
+            "fee": fee,
+        });
+        merge_options(&mut json, metadata)?;
+        merge_options(&mut json, options)?;
+        let res: NftMintResponse = self
+            .client
+            .cmd("nft_mint_nft", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match (res.nft_id, res.spend_bundle) {
+            (Some(nft_id), Some(spend_bundle)) => Ok(MintedNft {
+                nft_id,
+                spend_bundle,
+            }),
+            _ => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Mints one NFT per metadata entry in a single spend bundle. The bundle is not pushed,
+    /// so it can be signed or aggregated elsewhere first.
+    pub async fn nft_mint_bulk(
+        &self,
+        wallet_id: u64,
+        metadata_list: &[NftMetadata],
+        options: &NftMintBulkOptions,
+        fee: Mojos,
+    ) -> Result<MintedNfts> {
+        for address in options
+            .target_list
+            .iter()
+            .chain(&options.royalty_address)
+            .chain(&options.xch_change_target)
+        {
+            self.client.network.parse_address(address)?;
+        }
+        let mut json = json!({
+            "wallet_id": wallet_id,
+            "metadata_list": metadata_list,
+            "fee": fee,
+        });
+        merge_options(&mut json, options)?;
+        let res: NftMintBulkResponse = self
+            .client
+            .cmd("nft_mint_bulk", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match (res.nft_id_list, res.spend_bundle) {
+            (Some(nft_id_list), Some(spend_bundle)) => Ok(MintedNfts {
+                nft_id_list,
+                spend_bundle,
+            }),
+            _ => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn nft_transfer_nft(
+        &self,
+        wallet_id: u64,
+        nft_coin_id: &str,
+        target_address: &str,
+        fee: Mojos,
+    ) -> Result<SpendBundle> {
+        let target_address = self.client.network.parse_address(target_address)?;
+        let json = json!({
+            "wallet_id": wallet_id,
+            "nft_coin_id": nft_coin_id,
+            "target_address": target_address,
+            "fee": fee,
+        });
+        self.nft_spend("nft_transfer_nft", json).await
+    }
+
+    /// Assigns the NFT to the DID `did_id` (a `did:chia:1...` ID), or unassigns it if empty.
+    pub async fn nft_set_nft_did(
+        &self,
+        wallet_id: u64,
+        nft_coin_id: &str,
+        did_id: &str,
+        fee: Mojos,
+    ) -> Result<SpendBundle> {
+        let json = json!({
+            "wallet_id": wallet_id,
+            "nft_coin_id": nft_coin_id,
+            "did_id": did_id,
+            "fee": fee,
+        });
+        self.nft_spend("nft_set_nft_did", json).await
+    }
+
+    pub async fn nft_add_uri(
+        &self,
+        wallet_id: u64,
+        nft_coin_id: &str,
+        kind: NftUriKind,
+        uri: &str,
+        fee: Mojos,
+    ) -> Result<SpendBundle> {
+        let json = json!({
+            "wallet_id": wallet_id,
+            "nft_coin_id": nft_coin_id,
+            "key": kind,
+            "uri": uri,
+            "fee": fee,
+        });
+        self.nft_spend("nft_add_uri", json).await
+    }
+
+    async fn nft_spend(&self, command: &str, json: Value) -> Result<SpendBundle> {
+        let res: SpendBundleResponse = self
+            .client
+            .cmd(command, Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.spend_bundle {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Finds the NFT wallet holding the NFTs assigned to a DID.
+    pub async fn nft_get_by_did(&self, did_id: &str) -> Result<u64> {
+        let json = json!({
+            "did_id": did_id,
+        });
+        let res: WalletIdResponse = self
+            .client
+            .cmd("nft_get_by_did", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.wallet_id {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Computes the royalties owed when `royalty_assets` are traded for `fungible_assets`.
+    pub async fn nft_calculate_royalties(
+        &self,
+        royalty_assets: &[RoyaltyAsset],
+        fungible_assets: &[FungibleAsset],
+    ) -> Result<HashMap<String, Vec<RoyaltyPayment>>> {
+        let json = json!({
+            "royalty_assets": royalty_assets,
+            "fungible_assets": fungible_assets,
+        });
+        let res: RoyaltiesResponse = self
+            .client
+            .cmd("nft_calculate_royalties", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.error {
+            Some(e) => Err(anyhow!("{:#?}", e)),
+            None => Ok(res.royalties),
+        }
+    }
+
+    /// Creates an offer from amounts keyed by wallet ID or asset ID: negative amounts are
+    /// offered, positive ones requested.
+    pub async fn create_offer_for_ids(
+        &self,
+        offer: &HashMap<String, i64>,
+        fee: Mojos,
+        options: &CreateOfferOptions,
+    ) -> Result<CreatedOffer> {
+        let mut json = json!({
+            "offer": offer,
+            "fee": fee,
+        });
+        merge_options(&mut json, options)?;
+        let res: TradeRecordResponse = self
+            .client
+            .cmd("create_offer_for_ids", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match (res.offer, res.trade_record) {
+            (Some(offer), Some(trade_record)) => Ok(CreatedOffer {
+                offer,
+                trade_record,
+            }),
+            _ => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn take_offer(
+        &self,
+        offer: &str,
+        fee: Mojos,
+        coin_selection: &CoinSelection,
+    ) -> Result<TradeRecord> {
+        let mut json = json!({
+            "offer": offer,
+            "fee": fee,
+        });
+        merge_options(&mut json, coin_selection)?;
+        let res: TradeRecordResponse = self
+            .client
+            .cmd("take_offer", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.trade_record {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Cancels an offer. A secure cancel spends the offered coins on chain, paying `fee`; an
+    /// insecure one only forgets the offer, which can still be taken by anyone holding it.
+    pub async fn cancel_offer(&self, trade_id: &Bytes32, secure: bool, fee: Mojos) -> Result<()> {
+        let json = json!({
+            "trade_id": trade_id,
+            "secure": secure,
+            "fee": fee,
+        });
+        let res: BasicResponse = self
+            .client
+            .cmd("cancel_offer", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.error {
+            Some(e) => Err(anyhow!("{:#?}", e)),
+            None => Ok(()),
+        }
+    }
+
+    /// Cancels every pending offer that involves `asset_id` (`xch` or a CAT's asset ID), or
+    /// all pending offers if it is `None`. `batch_fee` is paid per spend bundle of
+    /// `batch_size` offers.
+    pub async fn cancel_offers(
+        &self,
+        secure: bool,
+        asset_id: Option<&str>,
+        batch_fee: Mojos,
+        batch_size: Option<u64>,
+    ) -> Result<()> {
+        let mut json = json!({
+            "secure": secure,
+            "batch_fee": batch_fee,
+            "cancel_all": asset_id.is_none(),
+        });
+        if let Some(asset_id) = asset_id {
+            json["asset_id"] = Value::from(asset_id);
+        }
+        if let Some(batch_size) = batch_size {
+            json["batch_size"] = Value::from(batch_size);
+        }
+        let res: BasicResponse = self
+            .client
+            .cmd("cancel_offers", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.error {
+            Some(e) => Err(anyhow!("{:#?}", e)),
+            None => Ok(()),
+        }
+    }
+
+    /// Lists trades along with their offer files, which are empty unless `file_contents` is
+    /// set.
+    pub async fn get_all_offers(
+        &self,
+        options: &GetAllOffersOptions,
+    ) -> Result<Vec<(TradeRecord, Option<String>)>> {
+        let json = serde_json::to_value(options)?;
+        let res: TradeRecordsResponse = self
+            .client
+            .cmd("get_all_offers", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.trade_records {
+            Some(records) => {
+                let mut offers = res.offers.unwrap_or_default().into_iter();
+                Ok(records
+                    .into_iter()
+                    .map(|record| (record, offers.next()))
+                    .collect())
+            },
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn get_offer(
+        &self,
+        trade_id: &Bytes32,
+        file_contents: bool,
+    ) -> Result<(TradeRecord, Option<String>)> {
+        let json = json!({
+            "trade_id": trade_id,
+            "file_contents": file_contents,
+        });
+        let res: TradeRecordResponse = self
+            .client
+            .cmd("get_offer", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.trade_record {
+            Some(r) => Ok((r, res.offer)),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn get_offers_count(&self) -> Result<OffersCount> {
+        let res: OffersCountResponse = self
+            .client
+            .cmd("get_offers_count", None)
+            .await?
+            .json()
+            .await?;
+        match (res.total, res.my_offers_count, res.taken_offers_count) {
+            (Some(total), Some(my_offers_count), Some(taken_offers_count)) => Ok(OffersCount {
+                total,
+                my_offers_count,
+                taken_offers_count,
+            }),
+            _ => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Launches a DID holding `amount` mojos and creates its wallet.
+    pub async fn create_did_wallet(
+        &self,
+        amount: Mojos,
+        fee: Mojos,
+        options: &CreateDidOptions,
+    ) -> Result<DidWallet> {
+        let mut json = json!({
+            "wallet_type": "did_wallet",
+            "did_type": "new",
+            "amount": amount,
+            "fee": fee,
+        });
+        merge_options(&mut json, options)?;
+        let res: DidResponse = self
+            .client
+            .cmd("create_new_wallet", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match (res.wallet_id, res.my_did) {
+            (Some(wallet_id), Some(my_did)) => Ok(DidWallet { wallet_id, my_did }),
+            _ => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Returns the wallet's `did:chia:1...` ID.
+    pub async fn did_get_did(&self, wallet_id: u64) -> Result<String> {
+        let json = json!({
+            "wallet_id": wallet_id,
+        });
+        let res: DidResponse = self
+            .client
+            .cmd("did_get_did", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.my_did {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Looks up a DID by its `did:chia:1...` ID or a coin ID. With `latest`, the wallet follows
+    /// the singleton to its newest state.
+    pub async fn did_get_info(&self, coin_id: &str, latest: bool) -> Result<DidInfo> {
+        let json = json!({
+            "coin_id": coin_id,
+            "latest": latest,
+        });
+        let res: DidInfoResponse = self
+            .client
+            .cmd("did_get_info", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match (
+            res.did_id,
+            res.launcher_id,
+            res.latest_coin,
+            res.p2_address,
+            res.public_key,
+            res.num_verification,
+            res.metadata,
+            res.full_puzzle,
+            res.solution,
+            res.hints,
+        ) {
+            (
+                Some(did_id),
+                Some(launcher_id),
+                Some(latest_coin),
+                Some(p2_address),
+                Some(public_key),
+                Some(num_verification),
+                Some(metadata),
+                Some(full_puzzle),
+                Some(solution),
+                Some(hints),
+            ) => Ok(DidInfo {
+                did_id,
+                launcher_id,
+                latest_coin,
+                p2_address,
+                public_key,
+                recovery_list_hash: res.recovery_list_hash,
+                num_verification,
+                metadata,
+                full_puzzle,
+                solution,
+                hints,
+            }),
+            _ => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn did_update_metadata(
+        &self,
+        wallet_id: u64,
+        metadata: &HashMap<String, String>,
+        fee: Mojos,
+    ) -> Result<SpendBundle> {
+        let json = json!({
+            "wallet_id": wallet_id,
+            "metadata": metadata,
+            "fee": fee,
+        });
+        let res: SpendBundleResponse = self
+            .client
+            .cmd("did_update_metadata", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.spend_bundle {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Sends the DID to `address`, which must belong to the client's network. With
+    /// `with_recovery_info`, the new owner keeps the recovery list.
+    pub async fn did_transfer_did(
+        &self,
+        wallet_id: u64,
+        address: &str,
+        fee: Mojos,
+        with_recovery_info: bool,
+    ) -> Result<Transaction> {
+        let address = self.client.network.parse_address(address)?;
+        let json = json!({
+            "wallet_id": wallet_id,
+            "inner_address": address,
+            "fee": fee,
+            "with_recovery_info": with_recovery_info,
+        });
+        let res: TransactionResponse = self
+            .client
+            .cmd("did_transfer_did", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.transaction {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn did_get_recovery_list(&self, wallet_id: u64) -> Result<DidRecoveryList> {
+        let json = json!({
+            "wallet_id": wallet_id,
+        });
+        let res: DidRecoveryListResponse = self
+            .client
+            .cmd("did_get_recovery_list", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match (res.recovery_list, res.num_required) {
+            (Some(recovery_list), Some(num_required)) => Ok(DidRecoveryList {
+                recovery_list,
+                num_required,
+            }),
+            _ => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn did_update_recovery_ids(
+        &self,
+        wallet_id: u64,
+        new_list: &[String],
+        num_verifications_required: u64,
+        fee: Mojos,
+    ) -> Result<()> {
+        let json = json!({
+            "wallet_id": wallet_id,
+            "new_list": new_list,
+            "num_verifications_required": num_verifications_required,
+            "fee": fee,
+        });
+        let res: BasicResponse = self
+            .client
+            .cmd("did_update_recovery_ids", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.error {
+            Some(e) => Err(anyhow!("{:#?}", e)),
+            None => Ok(()),
+        }
+    }
+
+    /// Spends the DID to make coin and puzzle announcements, given as hex, e.g. to approve
+    /// another spend. The signed spend bundle is returned without being pushed, so it can be
+    /// aggregated with the spend it approves.
+    pub async fn did_message_spend(
+        &self,
+        wallet_id: u64,
+        coin_announcements: &[String],
+        puzzle_announcements: &[String],
+    ) -> Result<SpendBundle> {
+        let json = json!({
+            "wallet_id": wallet_id,
+            "coin_announcements": coin_announcements,
+            "puzzle_announcements": puzzle_announcements,
+        });
+        let res: SpendBundleResponse = self
+            .client
+            .cmd("did_message_spend", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.spend_bundle {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Recovers a DID the wallet lost track of, e.g. after a resync, by its `did:chia:1...` ID
+    /// or a coin ID, and returns its latest coin.
+    pub async fn did_find_lost_did(&self, coin_id: &str) -> Result<CoinId> {
+        let json = json!({
+            "coin_id": coin_id,
+        });
+        let res: LatestCoinIdResponse = self
+            .client
+            .cmd("did_find_lost_did", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.latest_coin_id {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Creates a plot NFT and the pool wallet that tracks it.
+    pub async fn create_pool_wallet(
+        &self,
+        initial_target_state: &InitialPoolState,
+        fee: Mojos,
+    ) -> Result<CreatedPoolWallet> {
+        let json = json!({
+            "wallet_type": "pool_wallet",
+            "mode": "new",
+            "initial_target_state": initial_target_state,
+            "fee": fee,
+        });
+        let res: CreatePoolWalletResponse = self
+            .client
+            .cmd("create_new_wallet", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match (
+            res.transaction,
+            res.launcher_id,
+            res.p2_singleton_puzzle_hash,
+            res.total_fee,
+        ) {
+            (
+                Some(transaction),
+                Some(launcher_id),
+                Some(p2_singleton_puzzle_hash),
+                Some(total_fee),
+            ) => Ok(CreatedPoolWallet {
+                transaction,
+                launcher_id,
+                p2_singleton_puzzle_hash,
+                total_fee,
+            }),
+            _ => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Points a plot NFT at a pool. When leaving another pool, the change only takes effect
+    /// after the current relative lock height.
+    pub async fn pw_join_pool(
+        &self,
+        wallet_id: u64,
+        target_puzzle_hash: &PuzzleHash,
+        pool_url: &str,
+        relative_lock_height: u32,
+        fee: Mojos,
+    ) -> Result<PoolWalletTransaction> {
+        let json = json!({
+            "wallet_id": wallet_id,
+            "target_puzzlehash": target_puzzle_hash,
+            "pool_url": pool_url,
+            "relative_lock_height": relative_lock_height,
+            "fee": fee,
+        });
+        self.pool_wallet_spend("pw_join_pool", json).await
+    }
+
+    /// Leaves the current pool to farm to the wallet itself.
+    pub async fn pw_self_pool(&self, wallet_id: u64, fee: Mojos) -> Result<PoolWalletTransaction> {
+        let json = json!({
+            "wallet_id": wallet_id,
+            "fee": fee,
+        });
+        self.pool_wallet_spend("pw_self_pool", json).await
+    }
+
+    /// Claims the block rewards of a self pooling plot NFT, returning the state before the
+    /// claim. `max_spends_in_tx` caps how many reward coins one transaction absorbs.
+    pub async fn pw_absorb_rewards(
+        &self,
+        wallet_id: u64,
+        fee: Mojos,
+        max_spends_in_tx: Option<u32>,
+    ) -> Result<(PoolWalletInfo, PoolWalletTransaction)> {
+        let mut json = json!({
+            "wallet_id": wallet_id,
+            "fee": fee,
+        });
+        if let Some(max_spends_in_tx) = max_spends_in_tx {
+            json["max_spends_in_tx"] = Value::from(max_spends_in_tx);
+        }
+        let res: PoolWalletTransactionResponse = self
+            .client
+            .cmd("pw_absorb_rewards", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match (res.state, res.transaction) {
+            (Some(state), Some(transaction)) => Ok((state, PoolWalletTransaction {
+                transaction,
+                fee_transaction: res.fee_transaction,
+                total_fee: res.total_fee,
+            })),
+            _ => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Returns the plot NFT's state and the pool wallet's pending transactions.
+    pub async fn pw_status(&self, wallet_id: u64) -> Result<(PoolWalletInfo, Vec<Transaction>)> {
+        let json = json!({
+            "wallet_id": wallet_id,
+        });
+        let res: PoolWalletStatusResponse = self
+            .client
+            .cmd("pw_status", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.state {
+            Some(r) => Ok((r, res.unconfirmed_transactions.unwrap_or_default())),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    async fn pool_wallet_spend(&self, command: &str, json: Value) -> Result<PoolWalletTransaction> {
+        let res: PoolWalletTransactionResponse = self
+            .client
+            .cmd(command, Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.transaction {
+            Some(transaction) => Ok(PoolWalletTransaction {
+                transaction,
+                fee_transaction: res.fee_transaction,
+                total_fee: res.total_fee,
+            }),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Signs `message` with the key behind `address`. `is_hex` signs the hex decoded bytes;
+    /// `safe_mode` adds the CHIP-0002 prefix so the signature cannot authorize a spend.
+    pub async fn sign_message_by_address(
+        &self,
+        address: &str,
+        message: &str,
+        is_hex: bool,
+        safe_mode: bool,
+    ) -> Result<SignedMessage> {
+        let address = self.client.network.parse_address(address)?;
+        let json = json!({
+            "address": address,
+            "message": message,
+            "is_hex": is_hex,
+            "safe_mode": safe_mode,
+        });
+        self.sign_message("sign_message_by_address", json).await
+    }
+
+    /// Signs `message` with the owner key of a DID or NFT, given by its `did:chia:1...` or
+    /// `nft1...` ID.
+    pub async fn sign_message_by_id(
+        &self,
+        id: &str,
+        message: &str,
+        is_hex: bool,
+        safe_mode: bool,
+    ) -> Result<SignedMessage> {
+        let json = json!({
+            "id": id,
+            "message": message,
+            "is_hex": is_hex,
+            "safe_mode": safe_mode,
+        });
+        self.sign_message("sign_message_by_id", json).await
+    }
+
+    async fn sign_message(&self, command: &str, json: Value) -> Result<SignedMessage> {
+        let safe_mode = json["safe_mode"].as_bool().unwrap_or(true);
+        let is_hex = json["is_hex"].as_bool().unwrap_or(false);
+        let res: SignMessageResponse = self
+            .client
+            .cmd(command, Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match (res.pubkey, res.signature) {
+            (Some(pubkey), Some(signature)) => Ok(SignedMessage {
+                pubkey,
+                signature,
+                // Older wallets do not report the mode they signed with.
+                signing_mode: res
+                    .signing_mode
+                    .unwrap_or_else(|| SigningMode::for_request(is_hex, safe_mode)),
+                latest_coin_id: res.latest_coin_id,
+            }),
+            _ => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Asks the wallet to verify a signature. [`crate::bls::verify_signed_message`] does the
+    /// same without a wallet. With `address`, the key must also control that address.
+    pub async fn verify_signature(
+        &self,
+        pubkey: &str,
+        message: &str,
+        signature: &str,
+        signing_mode: SigningMode,
+        address: Option<&str>,
+    ) -> Result<bool> {
+        let mut json = json!({
+            "pubkey": pubkey,
+            "message": message,
+            "signature": signature,
+            "signing_mode": signing_mode,
+        });
+        if let Some(address) = address {
+            json["address"] = json!(self.client.network.parse_address(address)?);
+        }
+        let res: VerifySignatureResponse = self
+            .client
+            .cmd("verify_signature", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.is_valid {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Lists the coins a wallet can spend, within the limits of `coin_selection`.
+    pub async fn get_spendable_coins(
+        &self,
+        wallet_id: u64,
+        coin_selection: &CoinSelection,
+    ) -> Result<SpendableCoins> {
+        let mut json = json!({
+            "wallet_id": wallet_id,
+        });
+        merge_options(&mut json, coin_selection)?;
+        let res: SpendableCoinsResponse = self
+            .client
+            .cmd("get_spendable_coins", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match (
+            res.confirmed_records,
+            res.unconfirmed_removals,
+            res.unconfirmed_additions,
+        ) {
+            (Some(confirmed_records), Some(unconfirmed_removals), Some(unconfirmed_additions)) => {
+                Ok(SpendableCoins {
+                    confirmed_records,
+                    unconfirmed_removals,
+                    unconfirmed_additions,
+                })
+            },
+            _ => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Asks the wallet which coins it would spend to pay `amount`.
+    pub async fn select_coins(
+        &self,
+        wallet_id: u64,
+        amount: Mojos,
+        coin_selection: &CoinSelection,
+    ) -> Result<Vec<Coin>> {
+        let mut json = json!({
+            "wallet_id": wallet_id,
+            "amount": amount,
+        });
+        merge_options(&mut json, coin_selection)?;
+        let res: SelectCoinsResponse = self
+            .client
+            .cmd("select_coins", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.coins {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Searches the coins of all wallets. Returns the records and, if `include_total_count` is
+    /// set, the number of records matching regardless of paging.
+    pub async fn get_coin_records(
+        &self,
+        options: &GetCoinRecordsOptions,
+    ) -> Result<(Vec<WalletCoinRecord>, Option<u32>)> {
+        let mut json = json!({});
+        merge_options(&mut json, options)?;
+        let res: WalletCoinRecordsResponse = self
+            .client
+            .cmd("get_coin_records", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.coin_records {
+            Some(r) => Ok((r, res.total_count)),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Looks up coins by ID. Unlike the full node's endpoint, this only finds coins the wallet
+    /// tracks.
+    pub async fn get_coin_records_by_names(
+        &self,
+        names: &[CoinId],
+        start_height: Option<u32>,
+        end_height: Option<u32>,
+        include_spent_coins: bool,
+    ) -> Result<Vec<CoinRecord>> {
+        let mut json = json!({
+            "names": names,
+            "include_spent_coins": include_spent_coins,
+        });
+        if let Some(start_height) = start_height {
+            json["start_height"] = Value::from(start_height);
+        }
+        if let Some(end_height) = end_height {
+            json["end_height"] = Value::from(end_height);
+        }
+        let res: CoinRecordsResponse = self
+            .client
+            .cmd("get_coin_records_by_names", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.coin_records {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Combines coins of a wallet into one. [`crate::coin_selection::plan_consolidation`]
+    /// picks batches that fit within the cost limit.
+    pub async fn combine_coins(
+        &self,
+        wallet_id: u64,
+        fee: Mojos,
+        options: &CombineCoinsOptions,
+    ) -> Result<Vec<Transaction>> {
+        let mut json = json!({
+            "wallet_id": wallet_id,
+            "fee": fee,
+        });
+        merge_options(&mut json, options)?;
+        let res: TransactionsResponse = self
+            .client
+            .cmd("combine_coins", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.transactions {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Splits a coin into `number_of_coins` coins of `amount_per_coin`, with the remainder
+    /// going to change.
+    pub async fn split_coins(
+        &self,
+        wallet_id: u64,
+        target_coin_id: &CoinId,
+        number_of_coins: u16,
+        amount_per_coin: Mojos,
+        fee: Mojos,
+    ) -> Result<Vec<Transaction>> {
+        let json = json!({
+            "wallet_id": wallet_id,
+            "target_coin_id": target_coin_id,
+            "number_of_coins": number_of_coins,
+            "amount_per_coin": amount_per_coin,
+            "fee": fee,
+        });
+        let res: TransactionsResponse = self
+            .client
+            .cmd("split_coins", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.transactions {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn get_wallet_balance(&self, wallet_id: u64) -> Result<WalletBalance> {
+        let json = json!({
+        "wallet_id": wallet_id,
+        });
+
+        let res: GetWalletBalanceResponse = self
+            .client
+            .cmd("get_wallet_balance", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+
+        match res.wallet_balance {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn get_offer_summary(&self, offer: &str) -> Result<OfferSummary> {
+        let json = json!({
+        "offer": offer,
+        });
+        let res: OfferSummaryResponse = self
+            .client
+            .cmd("get_offer_summary", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.summary {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn check_offer_validity(&self, offer: &str) -> Result<OfferValidityResponse> {
+        let json = json!({
+        "offer": offer,
+        });
+        Ok(self
+            .client
+            .cmd("check_offer_validity", Some(json.to_string()))
+            .await?
+            .json()
+            .await?)
+    }
+
+    pub async fn get_connections(&self) -> Result<Vec<Connection>> {
+        let res: ConnectionsResponse = self
+            .client
+            .cmd("get_connections", None)
+            .await?
+            .json()
+            .await?;
+        match res.connections {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    pub async fn open_connection(&self, host: &str, port: u16) -> Result<()> {
+        let json = json!({
+            "host": host,
+            "port": port,
+        });
+        let res: BasicResponse = self
+            .client
+            .cmd("open_connection", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.error {
+            Some(e) => Err(anyhow!("{:#?}", e)),
+            None => Ok(()),
+        }
+    }
+
+    pub async fn close_connection(&self, node_id: &str) -> Result<()> {
+        let json = json!({
+            "node_id": node_id,
+        });
+        let res: BasicResponse = self
+            .client
+            .cmd("close_connection", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.error {
+            Some(e) => Err(anyhow!("{:#?}", e)),
+            None => Ok(()),
+        }
+    }
+
+    pub async fn get_healthz(&self) -> Result<bool, Error> {
+        let res: HealthzResponse = self.client.cmd("healthz", None).await?.json().await?;
+        Ok(res.success)
+    }
+}
+
+/// Copies the fields of an options struct into a request body, leaving out unset ones.
+fn merge_options(json: &mut Value, options: &impl Serialize) -> Result<()> {
+    if let (Value::Object(json), Value::Object(options)) = (json, serde_json::to_value(options)?) {
+        json.extend(options);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn confirmation_options_are_validated() {
+        assert!(ConfirmationOptions::default().validate().is_ok());
+        for backoff in [0.5, -1.0, f64::NAN, f64::INFINITY] {
+            let options = ConfirmationOptions {
+                backoff,
+                ..Default::default()
+            };
+            assert!(
+                matches!(
+                    options.validate(),
+                    Err(Error::InvalidConfirmationOptions(_))
+                ),
+                "{}",
+                backoff
+            );
+        }
+        let options = ConfirmationOptions {
+            poll_interval: Duration::ZERO,
+            ..Default::default()
+        };
+        assert!(matches!(
+            options.validate(),
+            Err(Error::InvalidConfirmationOptions(_))
+        ));
+    }
+
+    #[test]
+    fn poll_interval_backs_off_up_to_the_maximum() {
+        let options = ConfirmationOptions::default();
+        assert_eq!(
+            options.next_interval(Duration::from_secs(2)),
+            Duration::from_secs(3)
+        );
+        assert_eq!(
+            options.next_interval(Duration::from_secs(25)),
+            Duration::from_secs(30)
+        );
+        let options = ConfirmationOptions {
+            backoff: f64::MAX,
+            max_poll_interval: Duration::MAX,
+            ..Default::default()
+        };
+        assert_eq!(
+            options.next_interval(Duration::from_secs(30)),
+            Duration::MAX
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! The Verifiable Credential endpoints of the wallet, reached through [`super::Rpc::vc`].
+
+use super::merge_options;
+use crate::prelude::*;
+
+pub struct Rpc<'a> {
+    pub(super) client: &'a Client,
+}
+
+impl Rpc<'_> {
+    /// Mints a credential whose proofs the DID `did_id` (`did:chia:1...`) provides, to
+    /// `target_address` or the wallet itself. `vc_mint`.
+    pub async fn mint(
+        &self,
+        did_id: &str,
+        target_address: Option<&str>,
+        fee: Mojos,
+    ) -> Result<(VcRecord, Vec<Transaction>)> {
+        let mut json = json!({
+            "did_id": did_id,
+            "fee": fee,
+        });
+        if let Some(target_address) = target_address {
+            json["target_address"] = json!(self.client.network.parse_address(target_address)?);
+        }
+        let res: VcRecordResponse = self
+            .client
+            .cmd("vc_mint", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.vc_record {
+            Some(r) => Ok((r, res.transactions.unwrap_or_default())),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Looks up a credential by its launcher ID, returning `None` if the wallet does not hold
+    /// it. `vc_get`.
+    pub async fn get(&self, vc_id: &Bytes32) -> Result<Option<VcRecord>> {
+        let json = json!({
+            "vc_id": vc_id,
+        });
+        let res: VcRecordResponse = self
+            .client
+            .cmd("vc_get", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        if res.success {
+            Ok(res.vc_record)
+        } else {
+            Err(anyhow!("{:#?}", res.error))
+        }
+    }
+
+    /// Lists the wallet's credentials, paged by `start` and `end`. `vc_get_list`.
+    pub async fn get_list(&self, start: Option<u32>, end: Option<u32>) -> Result<VcList> {
+        let mut json = json!({});
+        if let Some(start) = start {
+            json["start"] = Value::from(start);
+        }
+        if let Some(end) = end {
+            json["end"] = Value::from(end);
+        }
+        let res: VcListResponse = self
+            .client
+            .cmd("vc_get_list", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match (res.vc_records, res.proofs) {
+            (Some(vc_records), Some(proofs)) => Ok(VcList { vc_records, proofs }),
+            _ => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Spends a credential, e.g. to move it or to update its proofs. `vc_spend`.
+    pub async fn spend(
+        &self,
+        vc_id: &Bytes32,
+        options: &VcSpendOptions,
+        fee: Mojos,
+    ) -> Result<Vec<Transaction>> {
+        let mut json = json!({
+            "vc_id": vc_id,
+            "fee": fee,
+        });
+        merge_options(&mut json, options)?;
+        let res: TransactionsResponse = self
+            .client
+            .cmd("vc_spend", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.transactions {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Revokes a credential this wallet's DID provides, given the parent of the credential's
+    /// current coin. `vc_revoke`.
+    pub async fn revoke(&self, vc_parent_id: &CoinId, fee: Mojos) -> Result<Vec<Transaction>> {
+        let json = json!({
+            "vc_parent_id": vc_parent_id,
+            "fee": fee,
+        });
+        let res: TransactionsResponse = self
+            .client
+            .cmd("vc_revoke", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.transactions {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+
+    /// Stores proofs in the wallet, so that credentials committing to their root can show
+    /// them. `vc_add_proofs`.
+    pub async fn add_proofs(&self, proofs: &HashMap<String, String>) -> Result<()> {
+        let json = json!({
+            "proofs": proofs,
+        });
+        let res: BasicResponse = self
+            .client
+            .cmd("vc_add_proofs", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.error {
+            Some(e) => Err(anyhow!("{:#?}", e)),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the stored proofs whose root is `root`. `vc_get_proofs_for_root`.
+    pub async fn get_proofs_for_root(&self, root: &Bytes32) -> Result<HashMap<String, String>> {
+        let json = json!({
+            "root": root,
+        });
+        let res: VcProofsResponse = self
+            .client
+            .cmd("vc_get_proofs_for_root", Some(json.to_string()))
+            .await?
+            .json()
+            .await?;
+        match res.proofs {
+            Some(r) => Ok(r),
+            None => Err(anyhow!("{:#?}", res.error)),
+        }
+    }
+}